@@ -0,0 +1,106 @@
+//! Scores every pair of Classic codes three ways and prints how long each
+//! takes: the original `Vec`-based scorer, the allocation-free
+//! [`Code::score`], and [`FeedbackTable`] lookups.
+//!
+//! Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ciphermind::code::Code;
+use ciphermind::game::{Difficulty, Feedback};
+use ciphermind::solver;
+use ciphermind::table::FeedbackTable;
+
+/// Times to repeat each full sweep of the code space.
+const ROUNDS: usize = 5;
+
+/// The scorer the game used before codes were typed: two `Vec`s per call and
+/// a `position`/`remove` search for the color matches.
+fn vec_score(secret: &[char], guess: &[char]) -> Feedback {
+    let mut exact_matches = 0;
+    let mut secret_remaining = Vec::new();
+    let mut guess_remaining = Vec::new();
+    for (&s, &g) in secret.iter().zip(guess) {
+        if g == s {
+            exact_matches += 1;
+        } else {
+            secret_remaining.push(s);
+            guess_remaining.push(g);
+        }
+    }
+
+    let mut color_matches = 0;
+    for &color in &guess_remaining {
+        if let Some(pos) = secret_remaining.iter().position(|&c| c == color) {
+            color_matches += 1;
+            secret_remaining.remove(pos);
+        }
+    }
+    Feedback {
+        exact_matches,
+        color_matches,
+    }
+}
+
+/// Run `sweep` over every pair `ROUNDS` times, returning the time per pair.
+fn time(pairs: usize, mut sweep: impl FnMut() -> usize) -> Duration {
+    let start = Instant::now();
+    let mut checksum = 0;
+    for _ in 0..ROUNDS {
+        checksum += sweep();
+    }
+    black_box(checksum);
+    start.elapsed() / (pairs * ROUNDS) as u32
+}
+
+fn main() {
+    let difficulty = Difficulty::CLASSIC;
+    let codes = solver::all_codes(&difficulty).unwrap();
+    let letters: Vec<Vec<char>> = codes.iter().map(|code| code.symbols().collect()).collect();
+    let indexes: Vec<usize> = codes.iter().map(Code::index).collect();
+    let pairs = codes.len() * codes.len();
+    let exact = |feedback: Feedback| feedback.exact_matches;
+
+    let vec_time = time(pairs, || {
+        let mut total = 0;
+        for secret in &letters {
+            for guess in &letters {
+                total += exact(vec_score(black_box(secret), black_box(guess)));
+            }
+        }
+        total
+    });
+
+    let code_time = time(pairs, || {
+        let mut total = 0;
+        for secret in &codes {
+            for guess in &codes {
+                total += exact(black_box(secret).score(black_box(guess)).unwrap());
+            }
+        }
+        total
+    });
+
+    let table = FeedbackTable::new(&difficulty).expect("Classic fits in a table");
+    let start = Instant::now();
+    table.feedback(0, 0);
+    let build_time = start.elapsed();
+    let table_time = time(pairs, || {
+        let mut total = 0;
+        for &secret in &indexes {
+            for &guess in &indexes {
+                total += exact(table.feedback(black_box(secret), black_box(guess)));
+            }
+        }
+        total
+    });
+
+    println!("Scoring all {} Classic pairs, {} rounds:", pairs, ROUNDS);
+    println!("  Vec<char> scorer    {:>8.1?} per pair", vec_time);
+    println!("  Code::score         {:>8.1?} per pair", code_time);
+    println!(
+        "  FeedbackTable       {:>8.1?} per pair (built once in {:.1?})",
+        table_time, build_time
+    );
+}
@@ -0,0 +1,127 @@
+//! Post-game analysis: grade every guess against the Knuth solver — no
+//! terminal I/O lives here.
+
+use crate::code::Code;
+use crate::game::{Difficulty, Feedback, TurnRecord};
+use crate::solver::{self, SolverError, Turn};
+
+/// How one guess of a finished game measured up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuessReview {
+    /// The guess as played.
+    pub guess: Code,
+    /// The feedback it earned.
+    pub feedback: Feedback,
+    /// Whether the guess could still have been the secret given earlier feedback.
+    pub consistent: bool,
+    /// Codes still possible before the guess was played.
+    pub candidates_before: usize,
+    /// Codes still possible after its feedback.
+    pub candidates_after: usize,
+    /// Most codes the guess could have left, whatever the secret.
+    pub worst_case: usize,
+    /// Most codes the solver's own pick could have left from the same position.
+    pub solver_worst_case: usize,
+    /// Guesses Knuth's solver takes to crack the secret from the same
+    /// position: an upper bound on the moves needed, not the optimum.
+    pub knuth_upper_bound: usize,
+}
+
+/// Review every turn of a game played against `secret`, in order. Fails if
+/// the code space is too large to search or a code doesn't fit the difficulty.
+pub fn review_game(
+    difficulty: &Difficulty,
+    secret: &Code,
+    history: &[TurnRecord],
+) -> Result<Vec<GuessReview>, SolverError> {
+    let clues: Vec<Turn> = history
+        .iter()
+        .map(|turn| (turn.guess.clone(), turn.feedback))
+        .collect();
+    let mut candidates = solver::all_codes(difficulty)?;
+    let mut reviews = Vec::with_capacity(clues.len());
+
+    for (turn, (guess, feedback)) in clues.iter().enumerate() {
+        let solver_guesses = solver::solve_from(difficulty, &clues[..turn], secret)?;
+        let solver_worst_case = solver_guesses
+            .first()
+            .map_or(0, |first| solver::worst_partition(first, &candidates));
+
+        let candidates_before = candidates.len();
+        let worst_case = solver::worst_partition(guess, &candidates);
+        let consistent = candidates.iter().any(|code| code == guess);
+        solver::narrow(difficulty, &mut candidates, guess, feedback)?;
+
+        reviews.push(GuessReview {
+            guess: guess.clone(),
+            feedback: *feedback,
+            consistent,
+            candidates_before,
+            candidates_after: candidates.len(),
+            worst_case,
+            solver_worst_case,
+            knuth_upper_bound: solver_guesses.len(),
+        });
+    }
+
+    Ok(reviews)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::code;
+    use crate::game::game_with_secret;
+
+    fn play(secret: &Code, guesses: &[&str]) -> Vec<TurnRecord> {
+        let mut game = game_with_secret(Difficulty::EASY, &secret.to_string());
+        for guess in guesses {
+            let guess = game.validate_guess(guess).unwrap();
+            game.submit_guess(&guess).unwrap();
+        }
+        game.history().to_vec()
+    }
+
+    #[test]
+    fn test_review_tracks_candidates() {
+        let secret = code("RGBY");
+        let history = play(&secret, &["RRGG", "RGYB", "RGBY"]);
+        let reviews = review_game(&Difficulty::EASY, &secret, &history).unwrap();
+
+        assert_eq!(reviews.len(), 3);
+        assert_eq!(reviews[0].candidates_before, 256);
+        for pair in reviews.windows(2) {
+            assert_eq!(pair[0].candidates_after, pair[1].candidates_before);
+        }
+        assert_eq!(reviews[2].candidates_after, 1);
+        assert!(reviews.iter().all(|r| r.knuth_upper_bound >= 1));
+        assert_eq!(reviews[2].knuth_upper_bound, 1);
+    }
+
+    #[test]
+    fn test_review_compares_worst_cases() {
+        let secret = code("RGBY");
+        let history = play(&secret, &["RRRR", "RRGG"]);
+        let reviews = review_game(&Difficulty::EASY, &secret, &history).unwrap();
+
+        // RRRR can leave 108 codes (every code with one red); RRGG at most 56.
+        assert_eq!(reviews[0].worst_case, 108);
+        assert_eq!(reviews[0].solver_worst_case, 56);
+        for review in &reviews {
+            assert!(review.candidates_after <= review.worst_case);
+        }
+    }
+
+    #[test]
+    fn test_review_flags_inconsistent_guesses() {
+        let secret = code("RGBY");
+        // Replaying a guess that already missed can't be the secret.
+        let history = play(&secret, &["RRGG", "RRGG", "RGBY"]);
+        let reviews = review_game(&Difficulty::EASY, &secret, &history).unwrap();
+
+        assert!(reviews[0].consistent);
+        assert!(!reviews[1].consistent);
+        assert_eq!(reviews[1].candidates_before, reviews[1].candidates_after);
+        assert!(reviews[2].consistent);
+    }
+}
@@ -0,0 +1,482 @@
+//! Full-screen game board drawn in place with crossterm raw mode.
+//!
+//! Guesses are built peg by peg with the keyboard rather than typed as
+//! letter strings, so only the difficulty's own symbols can be entered.
+//!
+//! While a [`Board`] is open it owns the terminal: raw mode and the alternate
+//! screen are switched on by [`Board::open`] and switched off again when it is
+//! dropped. A panic hook does the same first, so a crash still leaves a usable
+//! terminal with the panic message visible.
+
+use std::io::{self, IsTerminal, Stdout, Write};
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+    terminal::{self, Clear, ClearType},
+};
+
+use ciphermind::game::{self, Game};
+use crate::ui::{self, RenderStyle};
+
+/// Set while a board has the terminal, so the panic hook knows to restore it.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Rows around the guess rows: title, rules, gaps, legend, status, help.
+const CHROME_ROWS: u16 = 8;
+
+/// Width of the marker and number at the start of each guess row.
+const LABEL_WIDTH: usize = 6;
+
+/// Whether stdin and stdout are both terminals the board can take over.
+pub fn is_supported() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// The guess being built on the current row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Picker {
+    slots: Vec<Option<char>>,
+    cursor: usize,
+}
+
+/// What a key press did to the picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Press {
+    /// The row changed (or nothing happened); keep reading keys.
+    Editing,
+    /// Enter was pressed before every slot was filled.
+    Incomplete,
+    /// The key isn't a symbol of this game or a command.
+    Unknown(char),
+    /// A finished guess or a command to hand back to the game.
+    Done(String),
+}
+
+impl Picker {
+    fn new(code_length: usize) -> Picker {
+        Picker {
+            slots: vec![None; code_length],
+            cursor: 0,
+        }
+    }
+
+    /// Apply one key press, where `symbols` are the pegs this game allows.
+    fn press(&mut self, key: KeyEvent, symbols: &[char]) -> Press {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('c') => Press::Done("quit".to_string()),
+                _ => Press::Editing,
+            };
+        }
+
+        match key.code {
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.slots.len() - 1),
+            KeyCode::Up => self.cycle(symbols, true),
+            KeyCode::Down => self.cycle(symbols, false),
+            KeyCode::Backspace => self.clear(),
+            KeyCode::Delete => self.slots[self.cursor] = None,
+            KeyCode::Esc => *self = Picker::new(self.slots.len()),
+            KeyCode::Enter => {
+                return match self.guess() {
+                    Some(guess) => Press::Done(guess),
+                    None => Press::Incomplete,
+                }
+            }
+            KeyCode::Char(key) => {
+                let symbol = match key {
+                    '1'..='9' => symbols.get(key as usize - '1' as usize).copied(),
+                    ' ' => Some(game::BLANK).filter(|blank| symbols.contains(blank)),
+                    _ => Some(key.to_ascii_uppercase()).filter(|s| symbols.contains(s)),
+                };
+                match (symbol, key.to_ascii_lowercase()) {
+                    (Some(symbol), _) => self.set(symbol),
+                    (None, 'h') => return Press::Done("hint".to_string()),
+                    (None, 's') => return Press::Done("save".to_string()),
+                    (None, 'q') => return Press::Done("quit".to_string()),
+                    (None, _) => return Press::Unknown(key),
+                }
+            }
+            _ => {}
+        }
+        Press::Editing
+    }
+
+    /// Put `symbol` in the current slot and move on to the next.
+    fn set(&mut self, symbol: char) {
+        self.slots[self.cursor] = Some(symbol);
+        self.cursor = (self.cursor + 1).min(self.slots.len() - 1);
+    }
+
+    /// Clear the current slot, or step back and clear the previous one if
+    /// the current slot is already empty.
+    fn clear(&mut self) {
+        if self.slots[self.cursor].is_none() {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+        self.slots[self.cursor] = None;
+    }
+
+    /// Change the current slot to the next (or previous) symbol.
+    fn cycle(&mut self, symbols: &[char], forward: bool) {
+        let n = symbols.len();
+        let current = self.slots[self.cursor].and_then(|s| symbols.iter().position(|&c| c == s));
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % n,
+            (Some(i), false) => (i + n - 1) % n,
+            (None, true) => 0,
+            (None, false) => n - 1,
+        };
+        self.slots[self.cursor] = Some(symbols[next]);
+    }
+
+    /// The finished guess, once every slot is filled.
+    fn guess(&self) -> Option<String> {
+        self.slots.iter().copied().collect()
+    }
+}
+
+/// The full-screen board for one codebreaker game.
+pub struct Board {
+    out: Stdout,
+    status: String,
+    picker: Picker,
+}
+
+impl Board {
+    /// Take over the terminal for a game with `code_length` slots.
+    pub fn open(code_length: usize) -> io::Result<Board> {
+        install_panic_hook();
+        terminal::enable_raw_mode()?;
+        ACTIVE.store(true, Ordering::SeqCst);
+
+        let mut out = io::stdout();
+        if let Err(error) = execute!(out, terminal::EnterAlternateScreen, cursor::Hide) {
+            restore();
+            return Err(error);
+        }
+        Ok(Board {
+            out,
+            status: String::new(),
+            picker: Picker::new(code_length),
+        })
+    }
+
+    /// Replace the message on the status line.
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = status.into();
+    }
+
+    /// Move the picker's cursor to `slot`, e.g. the one a rejected guess
+    /// went wrong in.
+    pub fn focus_slot(&mut self, slot: usize) {
+        if slot < self.picker.slots.len() {
+            self.picker.cursor = slot;
+        }
+    }
+
+    /// Start the next row empty, once a guess has been accepted.
+    pub fn next_row(&mut self) {
+        self.picker = Picker::new(self.picker.slots.len());
+    }
+
+    /// Let the player build a guess peg by peg, redrawing after every key and
+    /// whenever the terminal is resized. Returns the guess as a letter string,
+    /// or `hint`, `save` or `quit`. The row is kept until [`Board::next_row`],
+    /// so a rejected guess can be fixed rather than re-entered.
+    pub fn read_command(&mut self, game: &Game) -> io::Result<String> {
+        let symbols = game.difficulty().symbols();
+        loop {
+            self.draw(game)?;
+            let Event::Key(key) = event::read()? else {
+                // Resizes (and anything else) just redraw.
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.picker.press(key, &symbols) {
+                Press::Editing => {}
+                Press::Incomplete => self.status = "Fill every slot before submitting.".to_string(),
+                Press::Unknown(key) => {
+                    self.status = format!("'{}' isn't one of this game's colors.", key);
+                }
+                Press::Done(command) => return Ok(command),
+            }
+        }
+    }
+
+    /// Show the finished board with `status` until a key is pressed, so the
+    /// last row and its feedback can be read before the board closes.
+    pub fn finish(&mut self, game: &Game, status: impl Into<String>) -> io::Result<()> {
+        self.status = status.into();
+        loop {
+            self.draw(game)?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Redraw the whole board for `game`.
+    pub fn draw(&mut self, game: &Game) -> io::Result<()> {
+        let d = game.difficulty();
+        let (_, height) = terminal::size()?;
+        queue!(self.out, Clear(ClearType::All))?;
+
+        // Once the game is over the last guess played is the one to show.
+        let guess = if game.is_over() {
+            game.attempts()
+        } else {
+            (game.attempts() + 1).min(d.max_attempts)
+        };
+        let title = format!(
+            "CIPHERMIND · {} · guess {} of {} · hints {}",
+            d.name,
+            guess,
+            d.max_attempts,
+            game.hints_used()
+        );
+        let rules = format!(
+            "{} slots · {}{}",
+            d.code_length,
+            d.repeats.describe(),
+            if d.blanks { " · blanks allowed" } else { "" }
+        );
+        let mut row = 0;
+        self.line(&mut row, &title)?;
+        self.line(&mut row, &rules)?;
+        row += 1;
+
+        // The key-peg grid takes two lines per row.
+        let row_height = if ui::feedback_style().shows_pegs() { 2 } else { 1 };
+        let visible = usize::from(height.saturating_sub(CHROME_ROWS) / row_height).max(1);
+        let (first, last) = visible_rows(game.attempts(), d.max_attempts, visible);
+        for i in first..last {
+            self.guess_row(row, game, i)?;
+            row += row_height;
+        }
+        row += 1;
+
+        queue!(self.out, cursor::MoveTo(0, row), Print("Colors: "))?;
+        for (i, symbol) in d.symbols().into_iter().enumerate() {
+            if i < 9 {
+                queue!(self.out, Print(format!("{}:", i + 1)))?;
+            }
+            self.peg(symbol)?;
+            queue!(self.out, Print(format!(" {}  ", symbol)))?;
+        }
+        row += 2;
+
+        let status = self.status.clone();
+        self.line(&mut row, &status)?;
+        queue!(self.out, cursor::MoveTo(0, row))?;
+        self.dim(if game.is_over() {
+            "Press any key to continue"
+        } else {
+            "←/→ slot · number, letter or ↑/↓ color · Backspace clear · Enter submit · \
+             h hint · s save · q quit"
+        })?;
+        self.out.flush()
+    }
+
+    /// One row of the board: the guess and its feedback, or empty slots.
+    fn guess_row(&mut self, row: u16, game: &Game, i: usize) -> io::Result<()> {
+        let current = i == game.attempts() && !game.is_over();
+        let marker = if current { ui::style().cursor() } else { ' ' };
+        let label = format!("{} {:>2}  ", marker, i + 1);
+        debug_assert_eq!(label.chars().count(), LABEL_WIDTH);
+        queue!(self.out, cursor::MoveTo(0, row), Print(label))?;
+
+        if current {
+            return self.picker_row();
+        }
+        let [_, _, empty] = ui::style().key_glyphs();
+        let Some(turn) = game.history().get(i) else {
+            return self.dim(&format!("{} ", empty).repeat(game.difficulty().code_length));
+        };
+        for symbol in turn.guess.symbols() {
+            self.peg(symbol)?;
+            queue!(self.out, Print(" "))?;
+        }
+        queue!(self.out, Print(" "))?;
+
+        let code_length = game.difficulty().code_length;
+        if ui::feedback_style().shows_pegs() {
+            let [top, bottom] =
+                ui::key_peg_grid(&turn.feedback, code_length, ui::style().key_glyphs());
+            // Past the label and the guess's pegs, each followed by a space.
+            let column = (LABEL_WIDTH + 2 * code_length + 1) as u16;
+            queue!(
+                self.out,
+                Print(&top),
+                cursor::MoveTo(column, row + 1),
+                Print(bottom),
+                cursor::MoveTo(column + top.chars().count() as u16, row)
+            )?;
+        }
+        if ui::feedback_style().shows_text() {
+            queue!(self.out, Print(format!("  {}", ui::feedback_text(&turn.feedback))))?;
+        }
+        Ok(())
+    }
+
+    /// The row being built, with the selected slot highlighted.
+    fn picker_row(&mut self) -> io::Result<()> {
+        let [_, _, empty] = ui::style().key_glyphs();
+        for i in 0..self.picker.slots.len() {
+            if i == self.picker.cursor {
+                queue!(self.out, SetAttribute(Attribute::Reverse))?;
+            }
+            match self.picker.slots[i] {
+                Some(symbol) => self.peg(symbol)?,
+                None => queue!(self.out, Print(empty))?,
+            }
+            queue!(self.out, SetAttribute(Attribute::Reset), Print(" "))?;
+        }
+        let letters: String = self.picker.slots.iter().map(|s| s.unwrap_or(empty)).collect();
+        queue!(self.out, Print(format!("  {}", letters)))
+    }
+
+    fn peg(&mut self, symbol: char) -> io::Result<()> {
+        match ui::style().peg(symbol) {
+            (Some(color), glyph) => {
+                queue!(self.out, SetForegroundColor(color), Print(glyph), ResetColor)
+            }
+            (None, glyph) => queue!(self.out, Print(glyph)),
+        }
+    }
+
+    /// Secondary text, greyed out unless the style has no color.
+    fn dim(&mut self, text: &str) -> io::Result<()> {
+        let text = ui::style().text(text);
+        if ui::style() == RenderStyle::Ascii {
+            return queue!(self.out, Print(text));
+        }
+        queue!(
+            self.out,
+            SetForegroundColor(Color::DarkGrey),
+            Print(text),
+            ResetColor
+        )
+    }
+
+    fn line(&mut self, row: &mut u16, text: &str) -> io::Result<()> {
+        queue!(self.out, cursor::MoveTo(0, *row), Print(ui::style().text(text)))?;
+        *row += 1;
+        Ok(())
+    }
+}
+
+impl Drop for Board {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Which guess rows fit in `visible` lines, keeping the current row on screen.
+fn visible_rows(attempts: usize, max_attempts: usize, visible: usize) -> (usize, usize) {
+    let current = attempts.min(max_attempts.saturating_sub(1));
+    let first = (current + 1).saturating_sub(visible);
+    (first, (first + visible).min(max_attempts))
+}
+
+/// Give the terminal back if a board has it.
+fn restore() {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Restore the terminal before the default hook prints a panic message.
+fn install_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            previous(info);
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_keys(picker: &mut Picker, symbols: &[char], keys: &str) -> Press {
+        let mut last = Press::Editing;
+        for c in keys.chars() {
+            last = picker.press(key(KeyCode::Char(c)), symbols);
+        }
+        last
+    }
+
+    #[test]
+    fn test_picker_builds_a_guess() {
+        let symbols = ['R', 'G', 'B', 'Y', 'M', 'C'];
+        let mut picker = Picker::new(4);
+        assert_eq!(type_keys(&mut picker, &symbols, "r2b"), Press::Editing);
+        assert_eq!(picker.press(key(KeyCode::Enter), &symbols), Press::Incomplete);
+
+        assert_eq!(type_keys(&mut picker, &symbols, "6"), Press::Editing);
+        assert_eq!(picker.press(key(KeyCode::Enter), &symbols), Press::Done("RGBC".to_string()));
+
+        picker.press(key(KeyCode::Left), &symbols);
+        picker.press(key(KeyCode::Up), &symbols);
+        assert_eq!(picker.guess(), Some("RGYC".to_string()));
+        picker.press(key(KeyCode::Down), &symbols);
+        picker.press(key(KeyCode::Down), &symbols);
+        assert_eq!(picker.guess(), Some("RGGC".to_string()));
+    }
+
+    #[test]
+    fn test_picker_only_accepts_legal_symbols() {
+        let symbols = ['R', 'G', 'B', 'Y'];
+        let mut picker = Picker::new(4);
+        assert_eq!(type_keys(&mut picker, &symbols, "m"), Press::Unknown('m'));
+        assert_eq!(type_keys(&mut picker, &symbols, "7"), Press::Unknown('7'));
+        assert_eq!(type_keys(&mut picker, &symbols, " "), Press::Unknown(' '));
+        assert_eq!(picker, Picker::new(4));
+
+        let with_blank = ['R', 'G', 'B', 'Y', game::BLANK];
+        type_keys(&mut picker, &with_blank, " 5");
+        assert_eq!(picker.slots[..2], [Some(game::BLANK), Some(game::BLANK)]);
+    }
+
+    #[test]
+    fn test_picker_backspace_and_commands() {
+        let symbols = ['R', 'G', 'B', 'Y'];
+        let mut picker = Picker::new(4);
+        type_keys(&mut picker, &symbols, "rg");
+        picker.press(key(KeyCode::Backspace), &symbols);
+        assert_eq!(picker.slots, [Some('R'), None, None, None]);
+        assert_eq!(picker.cursor, 1);
+
+        assert_eq!(type_keys(&mut picker, &symbols, "h"), Press::Done("hint".to_string()));
+        assert_eq!(type_keys(&mut picker, &symbols, "S"), Press::Done("save".to_string()));
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(picker.press(ctrl_c, &symbols), Press::Done("quit".to_string()));
+    }
+
+    #[test]
+    fn test_visible_rows_follow_the_current_guess() {
+        assert_eq!(visible_rows(0, 10, 20), (0, 10));
+        assert_eq!(visible_rows(3, 30, 10), (0, 10));
+        assert_eq!(visible_rows(15, 30, 10), (6, 16));
+        assert_eq!(visible_rows(30, 30, 10), (20, 30));
+    }
+}
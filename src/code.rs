@@ -0,0 +1,401 @@
+//! Pegs and codes: the typed values every secret and guess is made of — no
+//! terminal I/O lives here.
+//!
+//! A [`Code`] can only be built for a [`Difficulty`] it fits, so it always has
+//! one peg per slot and every peg comes from that difficulty's [`Palette`].
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::game::{Difficulty, Feedback, GuessError, BLANK, COLORS};
+
+/// How many different pegs exist: every color in [`COLORS`] plus the blank.
+pub const PEG_KINDS: usize = COLORS.len() + 1;
+
+/// One peg: a color from [`COLORS`] or the empty slot, [`Peg::BLANK`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Peg(u8);
+
+impl Peg {
+    /// The empty-slot peg, shown as [`BLANK`].
+    pub const BLANK: Peg = Peg(COLORS.len() as u8);
+
+    /// The color at `index` in [`COLORS`], if there is one.
+    pub fn color(index: usize) -> Option<Peg> {
+        (index < COLORS.len()).then_some(Peg(index as u8))
+    }
+
+    /// The peg shown as `symbol`. Letters must be uppercase.
+    pub fn from_symbol(symbol: char) -> Option<Peg> {
+        if symbol == BLANK {
+            return Some(Peg::BLANK);
+        }
+        COLORS.iter().position(|&color| color == symbol).and_then(Peg::color)
+    }
+
+    /// The letter the peg is shown as, or [`BLANK`].
+    pub fn symbol(self) -> char {
+        COLORS.get(usize::from(self.0)).copied().unwrap_or(BLANK)
+    }
+
+    /// The peg's place in [`COLORS`]; the blank comes after every color.
+    pub fn index(self) -> usize {
+        usize::from(self.0)
+    }
+}
+
+impl fmt::Display for Peg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// The pegs in play at a difficulty: a prefix of [`COLORS`], plus the blank
+/// when it is enabled. See [`Difficulty::palette`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette {
+    num_colors: u8,
+    blanks: bool,
+}
+
+impl Palette {
+    pub(crate) fn new(num_colors: usize, blanks: bool) -> Palette {
+        Palette {
+            num_colors: num_colors.min(COLORS.len()) as u8,
+            blanks,
+        }
+    }
+
+    /// Whether `peg` may be played.
+    pub fn contains(&self, peg: Peg) -> bool {
+        peg.0 < self.num_colors || (self.blanks && peg == Peg::BLANK)
+    }
+
+    /// How many pegs are in play, counting the blank.
+    pub fn size(&self) -> usize {
+        usize::from(self.num_colors) + usize::from(self.blanks)
+    }
+
+    /// Where `peg` sits in [`Palette::pegs`], if it is in play.
+    pub fn position(&self, peg: Peg) -> Option<usize> {
+        if peg.0 < self.num_colors {
+            Some(peg.index())
+        } else {
+            (self.blanks && peg == Peg::BLANK).then_some(usize::from(self.num_colors))
+        }
+    }
+
+    /// Where `code` sits in this palette's code space: its pegs' positions
+    /// read as a number in base [`Palette::size`], first slot most
+    /// significant. `None` if a peg is outside the palette.
+    pub fn code_index(&self, code: &Code) -> Option<usize> {
+        code.pegs.iter().try_fold(0, |index, &peg| {
+            Some(index * self.size() + self.position(peg)?)
+        })
+    }
+
+    /// Every peg in play: the colors in [`COLORS`] order, then the blank.
+    pub fn pegs(&self) -> Vec<Peg> {
+        let mut pegs: Vec<Peg> = (0..self.num_colors).map(Peg).collect();
+        if self.blanks {
+            pegs.push(Peg::BLANK);
+        }
+        pegs
+    }
+}
+
+/// A secret or a guess: one peg per slot, all from the palette it was built
+/// for. Codes with the same pegs are equal whatever their palettes.
+#[derive(Debug, Clone)]
+pub struct Code {
+    pegs: Vec<Peg>,
+    palette: Palette,
+}
+
+impl PartialEq for Code {
+    fn eq(&self, other: &Code) -> bool {
+        self.pegs == other.pegs
+    }
+}
+
+impl Eq for Code {}
+
+impl Hash for Code {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pegs.hash(state);
+    }
+}
+
+impl Code {
+    /// A code for `difficulty`, checked with [`Code::check`].
+    pub fn new(pegs: Vec<Peg>, difficulty: &Difficulty) -> Result<Code, GuessError> {
+        let code = Code::from_parts(pegs, difficulty.palette());
+        code.check(difficulty)?;
+        Ok(code)
+    }
+
+    /// Read a code typed as one letter per slot, in either case.
+    pub fn parse(text: &str, difficulty: &Difficulty) -> Result<Code, GuessError> {
+        let symbols: Vec<char> = text.chars().map(|ch| ch.to_ascii_uppercase()).collect();
+        if symbols.len() != difficulty.code_length {
+            return Err(GuessError::WrongLength {
+                expected: difficulty.code_length,
+                actual: symbols.len(),
+            });
+        }
+
+        let palette = difficulty.palette();
+        let mut pegs = Vec::with_capacity(symbols.len());
+        for (position, &symbol) in symbols.iter().enumerate() {
+            match Peg::from_symbol(symbol).filter(|&peg| palette.contains(peg)) {
+                Some(peg) => pegs.push(peg),
+                None => {
+                    return Err(GuessError::IllegalSymbol {
+                        symbol,
+                        position,
+                        palette,
+                    })
+                }
+            }
+        }
+        Ok(Code::from_parts(pegs, palette))
+    }
+
+    /// A code whose pegs are already known to fit `palette`.
+    pub(crate) fn from_parts(pegs: Vec<Peg>, palette: Palette) -> Code {
+        Code { pegs, palette }
+    }
+
+    /// Check that the code could be played at `difficulty`: one peg per slot,
+    /// each from its palette.
+    pub fn check(&self, difficulty: &Difficulty) -> Result<(), GuessError> {
+        if self.pegs.len() != difficulty.code_length {
+            return Err(GuessError::WrongLength {
+                expected: difficulty.code_length,
+                actual: self.pegs.len(),
+            });
+        }
+        let palette = difficulty.palette();
+        match self.pegs.iter().position(|&peg| !palette.contains(peg)) {
+            Some(position) => Err(GuessError::IllegalSymbol {
+                symbol: self.pegs[position].symbol(),
+                position,
+                palette,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The pegs, one per slot.
+    pub fn pegs(&self) -> &[Peg] {
+        &self.pegs
+    }
+
+    /// How many slots the code has.
+    pub fn len(&self) -> usize {
+        self.pegs.len()
+    }
+
+    /// Whether the code has no slots at all.
+    pub fn is_empty(&self) -> bool {
+        self.pegs.is_empty()
+    }
+
+    /// The palette the code was built for.
+    pub fn palette(&self) -> Palette {
+        self.palette
+    }
+
+    /// The letter of each peg, in slot order.
+    pub fn symbols(&self) -> impl Iterator<Item = char> + '_ {
+        self.pegs.iter().map(|peg| peg.symbol())
+    }
+
+    /// The first peg played twice, as the slots of its first and second use.
+    pub fn find_repeat(&self) -> Option<(usize, usize)> {
+        (1..self.pegs.len()).find_map(|position| {
+            let first = self.pegs[..position].iter().position(|&peg| peg == self.pegs[position]);
+            first.map(|first| (first, position))
+        })
+    }
+
+    /// The code's place in its palette's code space (see
+    /// [`Palette::code_index`]). With repeats allowed this is its position in
+    /// [`crate::solver::all_codes`].
+    pub fn index(&self) -> usize {
+        self.palette
+            .code_index(self)
+            .expect("a code's pegs come from its own palette")
+    }
+
+    /// The code at `index` in the code space of `difficulty` (see
+    /// [`Code::index`]), or `None` if the space is smaller than that.
+    pub fn from_index(index: usize, difficulty: &Difficulty) -> Option<Code> {
+        let palette = difficulty.palette();
+        let pegs = palette.pegs();
+        let mut rest = index;
+        let mut code = vec![Peg::BLANK; difficulty.code_length];
+        for slot in code.iter_mut().rev() {
+            *slot = pegs[rest % pegs.len()];
+            rest /= pegs.len();
+        }
+        (rest == 0).then(|| Code::from_parts(code, palette))
+    }
+
+    /// Score `guess` against this code as the secret. Fails if the two codes
+    /// have different lengths.
+    pub fn score(&self, guess: &Code) -> Result<Feedback, GuessError> {
+        if guess.len() != self.len() {
+            return Err(GuessError::WrongLength {
+                expected: self.len(),
+                actual: guess.len(),
+            });
+        }
+        Ok(score_pegs(&self.pegs, &guess.pegs))
+    }
+}
+
+/// Score `guess` against `secret` without allocating: exact matches first,
+/// then the color matches left over, counted per peg.
+fn score_pegs(secret: &[Peg], guess: &[Peg]) -> Feedback {
+    let mut exact_matches = 0;
+    let mut secret_counts = [0u8; PEG_KINDS];
+    let mut guess_counts = [0u8; PEG_KINDS];
+    for (&s, &g) in secret.iter().zip(guess) {
+        if s == g {
+            exact_matches += 1;
+        } else {
+            secret_counts[s.index()] += 1;
+            guess_counts[g.index()] += 1;
+        }
+    }
+
+    let color_matches = secret_counts
+        .iter()
+        .zip(&guess_counts)
+        .map(|(&s, &g)| usize::from(s.min(g)))
+        .sum();
+    Feedback {
+        exact_matches,
+        color_matches,
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.symbols().try_for_each(|symbol| write!(f, "{}", symbol))
+    }
+}
+
+/// Test shorthand for the code spelled by `text`, over every color and the
+/// blank.
+#[cfg(test)]
+pub(crate) fn code(text: &str) -> Code {
+    let pegs = text.chars().map(|ch| Peg::from_symbol(ch).unwrap()).collect();
+    Code::from_parts(pegs, Palette::new(COLORS.len(), true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoring_needs_equal_lengths() {
+        assert_eq!(
+            code("RGBY").score(&code("RGB")),
+            Err(GuessError::WrongLength { expected: 4, actual: 3 })
+        );
+    }
+
+    #[test]
+    fn test_pegs_round_trip_through_symbols() {
+        for symbol in COLORS.into_iter().chain([BLANK]) {
+            assert_eq!(Peg::from_symbol(symbol).unwrap().symbol(), symbol);
+        }
+        assert_eq!(Peg::from_symbol('r'), None);
+        assert_eq!(Peg::from_symbol('?'), None);
+        assert_eq!(Peg::color(COLORS.len()), None);
+        assert_eq!(Peg::BLANK.index(), COLORS.len());
+    }
+
+    #[test]
+    fn test_palette_follows_the_difficulty() {
+        let classic = Difficulty::CLASSIC.palette();
+        assert_eq!(classic.pegs().len(), 6);
+        assert!(classic.contains(Peg::from_symbol('C').unwrap()));
+        assert!(!classic.contains(Peg::from_symbol('W').unwrap()));
+        assert!(!classic.contains(Peg::BLANK));
+        assert!(Difficulty::CLASSIC.with_blanks(true).unwrap().palette().contains(Peg::BLANK));
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let parsed = Code::parse("rgBy", &Difficulty::CLASSIC).unwrap();
+        assert_eq!(parsed.to_string(), "RGBY");
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(parsed.palette(), Difficulty::CLASSIC.palette());
+        assert_eq!(
+            Code::parse("RGBW", &Difficulty::CLASSIC),
+            Err(GuessError::IllegalSymbol {
+                symbol: 'W',
+                position: 3,
+                palette: Difficulty::CLASSIC.palette(),
+            })
+        );
+        assert_eq!(
+            Code::parse("RGBYM", &Difficulty::CLASSIC),
+            Err(GuessError::WrongLength { expected: 4, actual: 5 })
+        );
+    }
+
+    #[test]
+    fn test_new_checks_the_difficulty() {
+        let pegs = code("RGBC").pegs().to_vec();
+        assert!(Code::new(pegs.clone(), &Difficulty::CLASSIC).is_ok());
+        assert_eq!(
+            Code::new(pegs.clone(), &Difficulty::EASY),
+            Err(GuessError::IllegalSymbol {
+                symbol: 'C',
+                position: 3,
+                palette: Difficulty::EASY.palette(),
+            })
+        );
+        assert_eq!(
+            Code::new(pegs, &Difficulty::HARD),
+            Err(GuessError::WrongLength { expected: 5, actual: 4 })
+        );
+    }
+
+    #[test]
+    fn test_index_round_trips() {
+        let blanks = Difficulty::CLASSIC.with_blanks(true).unwrap();
+        assert_eq!(code("RRRR").index(), 0);
+        for difficulty in [Difficulty::EASY, Difficulty::HARD, blanks] {
+            let space = difficulty.guess_space().unwrap();
+            for index in [0, 1, space / 2, space - 1] {
+                let code = Code::from_index(index, &difficulty).unwrap();
+                assert_eq!(code.check(&difficulty), Ok(()));
+                assert_eq!(code.index(), index);
+            }
+            assert_eq!(Code::from_index(space, &difficulty), None);
+        }
+        assert_eq!(Code::parse("___C", &blanks).unwrap().index(), 7usize.pow(4) - 2);
+        assert_eq!(Difficulty::EASY.palette().code_index(&code("RGBM")), None);
+    }
+
+    #[test]
+    fn test_find_repeat() {
+        assert_eq!(code("RGBY").find_repeat(), None);
+        assert_eq!(code("RGBG").find_repeat(), Some((1, 3)));
+    }
+
+    #[test]
+    fn test_score() {
+        let secret = code("RGBY");
+        let feedback = |guess| secret.score(&code(guess)).unwrap();
+        assert_eq!(feedback("RGBY"), Feedback { exact_matches: 4, color_matches: 0 });
+        assert_eq!(feedback("YBGR"), Feedback { exact_matches: 0, color_matches: 4 });
+        assert_eq!(feedback("RRGG"), Feedback { exact_matches: 1, color_matches: 1 });
+        assert_eq!(feedback("MCMC"), Feedback { exact_matches: 0, color_matches: 0 });
+    }
+}
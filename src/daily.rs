@@ -0,0 +1,166 @@
+//! The daily challenge: one shared secret per difficulty per calendar day —
+//! no terminal I/O lives here.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::game::{Difficulty, Feedback, TurnRecord};
+use crate::storage;
+
+/// A calendar date (proleptic Gregorian, UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    /// The year, negative before 1 BC.
+    pub year: i64,
+    /// The month, 1 to 12.
+    pub month: u32,
+    /// The day of the month, starting at 1.
+    pub day: u32,
+}
+
+impl Date {
+    /// Today's date in UTC, so every player shares the same puzzle.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Date::from_days_since_epoch((seconds / 86_400) as i64)
+    }
+
+    /// The date `days` after 1970-01-01 (Howard Hinnant's `civil_from_days`).
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Date { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The seed for the daily secret.
+pub fn seed(date: Date, difficulty: &Difficulty) -> u64 {
+    storage::fnv1a(format!("ciphermind-daily {} {}", date, difficulty.name).as_bytes())
+}
+
+/// Where finished daily challenges are recorded.
+pub fn played_path() -> PathBuf {
+    storage::data_dir().join("daily.txt")
+}
+
+/// The line recording one attempted challenge.
+fn entry(date: Date, difficulty: &Difficulty) -> String {
+    format!("{} {}", date, difficulty.name)
+}
+
+/// Whether today's challenge at this difficulty was already attempted.
+pub fn has_played(path: &Path, date: Date, difficulty: &Difficulty) -> io::Result<bool> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents.lines().any(|line| line == entry(date, difficulty))),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Remember that this challenge has been attempted.
+pub fn record_played(path: &Path, date: Date, difficulty: &Difficulty) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry(date, difficulty))
+}
+
+/// A spoiler-free summary of a finished daily game: one row of squares per
+/// guess (🟩 exact, 🟨 color, ⬛ miss), safe to paste into chat.
+pub fn share_text(date: Date, difficulty: &Difficulty, history: &[TurnRecord], won: bool) -> String {
+    let score = if won {
+        history.len().to_string()
+    } else {
+        "X".to_string()
+    };
+    let mut text = format!(
+        "CipherMind Daily {} {} {}/{}",
+        date, difficulty.name, score, difficulty.max_attempts
+    );
+
+    for turn in history {
+        let Feedback {
+            exact_matches,
+            color_matches,
+        } = turn.feedback;
+        let misses = difficulty.code_length - exact_matches - color_matches;
+        text.push('\n');
+        text.push_str(&"🟩".repeat(exact_matches));
+        text.push_str(&"🟨".repeat(color_matches));
+        text.push_str(&"⬛".repeat(misses));
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::code;
+    use crate::game::game_with_secret;
+    use std::env;
+
+    #[test]
+    fn test_dates_from_epoch_days() {
+        assert_eq!(Date::from_days_since_epoch(0).to_string(), "1970-01-01");
+        assert_eq!(Date::from_days_since_epoch(59).to_string(), "1970-03-01");
+        assert_eq!(Date::from_days_since_epoch(11_016).to_string(), "2000-02-29");
+        assert_eq!(Date::from_days_since_epoch(20_743).to_string(), "2026-10-17");
+    }
+
+    #[test]
+    fn test_seed_is_per_day_and_difficulty() {
+        let today = Date::from_days_since_epoch(20_743);
+        let tomorrow = Date::from_days_since_epoch(20_744);
+        assert_eq!(seed(today, &Difficulty::CLASSIC), seed(today, &Difficulty::CLASSIC));
+        assert_ne!(seed(today, &Difficulty::CLASSIC), seed(tomorrow, &Difficulty::CLASSIC));
+        assert_ne!(seed(today, &Difficulty::CLASSIC), seed(today, &Difficulty::HARD));
+    }
+
+    #[test]
+    fn test_played_days_are_remembered() {
+        let path = env::temp_dir()
+            .join(format!("ciphermind-daily-test-{}", std::process::id()))
+            .join("daily.txt");
+        let today = Date::from_days_since_epoch(20_743);
+
+        assert!(!has_played(&path, today, &Difficulty::CLASSIC).unwrap());
+        record_played(&path, today, &Difficulty::CLASSIC).unwrap();
+        assert!(has_played(&path, today, &Difficulty::CLASSIC).unwrap());
+        assert!(!has_played(&path, today, &Difficulty::HARD).unwrap());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_share_text_hides_the_code() {
+        let today = Date::from_days_since_epoch(20_743);
+        let mut game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        game.submit_guess(&code("RYMM")).unwrap();
+        game.submit_guess(&code("RGBY")).unwrap();
+
+        assert_eq!(
+            share_text(today, &Difficulty::CLASSIC, game.history(), true),
+            "CipherMind Daily 2026-10-17 Classic 2/10\n🟩🟨⬛⬛\n🟩🟩🟩🟩"
+        );
+    }
+}
@@ -1,192 +1,721 @@
 //! Core game logic for CipherMind — no terminal I/O lives here.
 
-use rand::seq::SliceRandom;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::code::{Code, Palette};
 
 /// Full color palette. Individual difficulties use a prefix of this list.
-pub const COLORS: [char; 6] = ['R', 'G', 'B', 'Y', 'M', 'C']; // Red, Green, Blue, Yellow, Magenta, Cyan
+pub const COLORS: [char; 12] = [
+    'R', 'G', 'B', 'Y', 'M', 'C', // Red, Green, Blue, Yellow, Magenta, Cyan
+    'W', 'O', 'P', 'K', 'N', 'L', // White, Orange, Purple, blacK, browN, Lime
+];
+
+/// The empty-slot peg, playable alongside the colors when blanks are enabled.
+pub const BLANK: char = '_';
+
+/// Shortest code a custom difficulty may use.
+pub const MIN_CODE_LENGTH: usize = 2;
+/// Longest code a custom difficulty may use.
+pub const MAX_CODE_LENGTH: usize = 10;
+/// Most guesses a custom difficulty may allow.
+pub const MAX_ATTEMPTS: usize = 30;
+
+/// Whether a color may appear more than once in a code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeats {
+    /// Secrets and guesses may repeat colors (the classic rules).
+    Allowed,
+    /// The secret never repeats a color, but guesses still may.
+    UniqueSecret,
+    /// Neither the secret nor any guess may repeat a color.
+    Forbidden,
+}
+
+impl Repeats {
+    /// Every option, in menu order.
+    pub const ALL: [Repeats; 3] = [Self::Allowed, Self::UniqueSecret, Self::Forbidden];
+
+    /// Short description for menus and the welcome banner.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Repeats::Allowed => "colors may repeat",
+            Repeats::UniqueSecret => "no repeats in the secret (guesses may repeat)",
+            Repeats::Forbidden => "unique colors only",
+        }
+    }
+}
 
 /// A difficulty preset: how long the code is, how many colors are in play,
-/// and how many guesses the player gets.
+/// how many guesses the player gets, whether colors may repeat, and whether
+/// slots may be left blank.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Difficulty {
+    /// "Easy", "Classic", "Hard" or "Custom".
     pub name: &'static str,
+    /// Slots in the secret and in every guess.
     pub code_length: usize,
+    /// How many of [`COLORS`] are in play, taken from the front.
     pub num_colors: usize,
+    /// Guesses allowed before the game is lost.
     pub max_attempts: usize,
+    /// Whether colors may repeat in the secret and in guesses.
+    pub repeats: Repeats,
+    /// Whether slots may hold [`BLANK`].
+    pub blanks: bool,
 }
 
 impl Difficulty {
+    /// Four slots, four colors, twelve guesses.
     pub const EASY: Difficulty = Difficulty {
         name: "Easy",
         code_length: 4,
         num_colors: 4,
         max_attempts: 12,
+        repeats: Repeats::Allowed,
+        blanks: false,
     };
+    /// The classic game: four slots, six colors, ten guesses.
     pub const CLASSIC: Difficulty = Difficulty {
         name: "Classic",
         code_length: 4,
         num_colors: 6,
         max_attempts: 10,
+        repeats: Repeats::Allowed,
+        blanks: false,
     };
+    /// Five slots, six colors, eight guesses.
     pub const HARD: Difficulty = Difficulty {
         name: "Hard",
         code_length: 5,
         num_colors: 6,
         max_attempts: 8,
+        repeats: Repeats::Allowed,
+        blanks: false,
     };
 
     /// The three presets, in menu order.
     pub const ALL: [Difficulty; 3] = [Self::EASY, Self::CLASSIC, Self::HARD];
 
+    /// A player-defined difficulty, checked with [`Difficulty::validate`].
+    pub fn custom(
+        code_length: usize,
+        num_colors: usize,
+        max_attempts: usize,
+    ) -> Result<Difficulty, DifficultyError> {
+        let difficulty = Difficulty {
+            name: "Custom",
+            code_length,
+            num_colors,
+            max_attempts,
+            repeats: Repeats::Allowed,
+            blanks: false,
+        };
+        difficulty.validate()?;
+        Ok(difficulty)
+    }
+
+    /// The same difficulty played under a different repeat rule.
+    pub fn with_repeats(self, repeats: Repeats) -> Result<Difficulty, DifficultyError> {
+        let difficulty = Difficulty { repeats, ..self };
+        difficulty.validate()?;
+        Ok(difficulty)
+    }
+
+    /// Check that a game can actually be played at this difficulty.
+    pub fn validate(&self) -> Result<(), DifficultyError> {
+        if !(MIN_CODE_LENGTH..=MAX_CODE_LENGTH).contains(&self.code_length) {
+            return Err(DifficultyError::CodeLength(self.code_length));
+        }
+        if !(2..=COLORS.len()).contains(&self.num_colors) {
+            return Err(DifficultyError::NumColors(self.num_colors));
+        }
+        if !(1..=MAX_ATTEMPTS).contains(&self.max_attempts) {
+            return Err(DifficultyError::MaxAttempts(self.max_attempts));
+        }
+        if self.repeats != Repeats::Allowed && self.num_symbols() < self.code_length {
+            return Err(DifficultyError::NotEnoughUniqueColors {
+                code_length: self.code_length,
+                num_colors: self.num_symbols(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The same difficulty with blank slots switched on or off. Turning them
+    /// off can leave too few symbols for a unique-colors rule.
+    pub fn with_blanks(self, blanks: bool) -> Result<Difficulty, DifficultyError> {
+        let difficulty = Difficulty { blanks, ..self };
+        difficulty.validate()?;
+        Ok(difficulty)
+    }
+
     /// The colors available at this difficulty (a prefix of [`COLORS`]).
     pub fn colors(&self) -> &'static [char] {
         &COLORS[..self.num_colors]
     }
+
+    /// Every peg a slot may hold: the colors, then the blank if enabled.
+    pub fn palette(&self) -> Palette {
+        Palette::new(self.num_colors, self.blanks)
+    }
+
+    /// Every symbol a slot may hold: the colors, then [`BLANK`] if enabled.
+    pub fn symbols(&self) -> Vec<char> {
+        self.palette().pegs().into_iter().map(|peg| peg.symbol()).collect()
+    }
+
+    /// Read a guess typed as one letter per slot and check it with
+    /// [`Difficulty::check_guess`].
+    pub fn parse_guess(&self, text: &str) -> Result<Code, GuessError> {
+        let guess = Code::parse(text, self)?;
+        self.check_guess(&guess)?;
+        Ok(guess)
+    }
+
+    /// Check that `guess` may be played: it must fit the difficulty (see
+    /// [`Code::check`]) and, under [`Repeats::Forbidden`], not repeat a color.
+    pub fn check_guess(&self, guess: &Code) -> Result<(), GuessError> {
+        guess.check(self)?;
+        if self.repeats != Repeats::Forbidden {
+            return Ok(());
+        }
+        match guess.find_repeat() {
+            Some((first, position)) => Err(GuessError::Duplicate {
+                symbol: guess.pegs()[position].symbol(),
+                first,
+                position,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// How many symbols a slot may hold, counting the blank.
+    pub fn num_symbols(&self) -> usize {
+        self.num_colors + usize::from(self.blanks)
+    }
+
+    /// How many distinct secret codes exist, or `None` if that overflows `usize`.
+    pub fn code_space(&self) -> Option<usize> {
+        match self.repeats {
+            Repeats::Allowed => self.guess_space(),
+            // n! / (n - k)! orderings of k distinct colors out of n.
+            Repeats::UniqueSecret | Repeats::Forbidden => (0..self.code_length)
+                .try_fold(1usize, |total, i| total.checked_mul(self.num_symbols().checked_sub(i)?)),
+        }
+    }
+
+    /// How many distinct guesses may be played, or `None` if that overflows `usize`.
+    pub fn guess_space(&self) -> Option<usize> {
+        match self.repeats {
+            Repeats::Allowed | Repeats::UniqueSecret => {
+                self.num_symbols().checked_pow(self.code_length as u32)
+            }
+            Repeats::Forbidden => self.code_space(),
+        }
+    }
+}
+
+/// The name followed by every rule, e.g. `Classic · 4 slots · 6 colors ·
+/// 10 guesses · colors may repeat`. Two difficulties print the same only if
+/// they play the same.
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} · {} slots · {} colors · {} guesses · {}",
+            self.name,
+            self.code_length,
+            self.num_colors,
+            self.max_attempts,
+            self.repeats.describe()
+        )?;
+        if self.blanks {
+            write!(f, " · blanks allowed")?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a custom difficulty can't be played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DifficultyError {
+    /// The code length is outside `MIN_CODE_LENGTH..=MAX_CODE_LENGTH`.
+    CodeLength(usize),
+    /// The palette is smaller than two colors or larger than [`COLORS`].
+    NumColors(usize),
+    /// The guess budget is zero or above `MAX_ATTEMPTS`.
+    MaxAttempts(usize),
+    /// Unique colors were asked for, but there are fewer colors than slots.
+    NotEnoughUniqueColors {
+        /// The requested code length.
+        code_length: usize,
+        /// The requested palette size.
+        num_colors: usize,
+    },
+}
+
+impl fmt::Display for DifficultyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DifficultyError::CodeLength(n) => write!(
+                f,
+                "code length {} is out of range — choose {} to {} slots",
+                n, MIN_CODE_LENGTH, MAX_CODE_LENGTH
+            ),
+            DifficultyError::NumColors(n) => write!(
+                f,
+                "{} colors is out of range — choose 2 to {} colors",
+                n,
+                COLORS.len()
+            ),
+            DifficultyError::MaxAttempts(n) => write!(
+                f,
+                "{} guesses is out of range — choose 1 to {} guesses",
+                n, MAX_ATTEMPTS
+            ),
+            DifficultyError::NotEnoughUniqueColors {
+                code_length,
+                num_colors,
+            } => write!(
+                f,
+                "{} slots can't each get a different color from only {} colors",
+                code_length, num_colors
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DifficultyError {}
+
+/// Who sets the secret and who breaks it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The computer picks a secret and the player guesses it.
+    Codebreaker,
+    /// The player thinks of a secret and the computer guesses it.
+    Codemaker,
+}
+
+/// Where a game stands. Every game starts [`GameState::InProgress`] and
+/// makes exactly one transition to one of the other states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    /// Guesses are still being taken.
+    InProgress,
+    /// The secret was guessed.
+    Won,
+    /// Every guess was used up.
+    Lost,
+    /// The player gave up before the game was decided.
+    Abandoned,
+}
+
+/// How a finished game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The secret was guessed.
+    Won,
+    /// Every guess was used up.
+    Lost,
+    /// The player quit before running out of guesses.
+    Abandoned,
+}
+
+impl Outcome {
+    /// Every outcome.
+    pub const ALL: [Outcome; 3] = [Outcome::Won, Outcome::Lost, Outcome::Abandoned];
+
+    /// A lowercase name, as written in game records.
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Won => "won",
+            Outcome::Lost => "lost",
+            Outcome::Abandoned => "abandoned",
+        }
+    }
+}
+
+/// Why a guess was rejected. Positions are 0-based slot indexes, so a front
+/// end can point at the offending slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    /// The guess doesn't have one symbol per slot.
+    WrongLength {
+        /// Slots in the code.
+        expected: usize,
+        /// Symbols in the guess.
+        actual: usize,
+    },
+    /// A slot holds a symbol that isn't in play at this difficulty.
+    IllegalSymbol {
+        /// The symbol as typed.
+        symbol: char,
+        /// The slot it was typed in.
+        position: usize,
+        /// The pegs that may be played instead.
+        palette: Palette,
+    },
+    /// A color is used twice while [`Repeats::Forbidden`] is in force.
+    Duplicate {
+        /// The repeated color.
+        symbol: char,
+        /// Where it first appears.
+        first: usize,
+        /// Where it appears again.
+        position: usize,
+    },
+    /// The game has already been won or abandoned.
+    GameOver,
+    /// Every guess has already been used.
+    OutOfAttempts,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::WrongLength { expected, actual } => write!(
+                f,
+                "Invalid length! Please enter exactly {} colors (got {}).",
+                expected, actual
+            ),
+            GuessError::IllegalSymbol {
+                symbol,
+                position,
+                palette,
+            } => {
+                let legal: String = palette.pegs().iter().map(|peg| peg.symbol()).collect();
+                write!(
+                    f,
+                    "Invalid color '{}' in slot {}. Use only: {}",
+                    symbol,
+                    position + 1,
+                    legal
+                )
+            }
+            GuessError::Duplicate {
+                symbol, position, ..
+            } => write!(
+                f,
+                "Color '{}' is used again in slot {}. Each color may appear only once.",
+                symbol,
+                position + 1
+            ),
+            GuessError::GameOver => write!(f, "This game is already over."),
+            GuessError::OutOfAttempts => write!(f, "There are no guesses left."),
+        }
+    }
 }
 
+impl std::error::Error for GuessError {}
+
 /// Represents the feedback for a guess
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Feedback {
-    pub exact_matches: usize, // Correct color in correct position
-    pub color_matches: usize, // Correct color in wrong position
+    /// Correct color in correct position
+    pub exact_matches: usize,
+    /// Correct color in wrong position
+    pub color_matches: usize,
+}
+
+/// One submitted guess, the feedback it earned, and when it was played.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnRecord {
+    /// The guess.
+    pub guess: Code,
+    /// The feedback it earned.
+    pub feedback: Feedback,
+    /// When it was submitted.
+    pub timestamp: SystemTime,
 }
 
-/// Main game state
+/// Main game state. The secret and its seed stay hidden from front ends until
+/// the game is over; see [`Game::secret`].
 pub struct Game {
-    pub secret_code: Vec<char>,
-    pub attempts: usize,
-    pub difficulty: Difficulty,
+    pub(crate) secret_code: Code,
+    difficulty: Difficulty,
+    hints_used: usize,
+    pub(crate) seed: Option<u64>,
+    /// When the game began, for timing.
+    pub started: SystemTime,
+    state: GameState,
+    history: Vec<TurnRecord>,
 }
 
 impl Game {
-    /// Create a new game with a random secret code for the given difficulty
+    /// Create a new game with a random secret code for the given difficulty.
+    /// A fresh seed is drawn so the game can be reproduced later.
     pub fn new(difficulty: Difficulty) -> Self {
-        let mut rng = rand::thread_rng();
-        let colors = difficulty.colors();
-        let secret_code: Vec<char> = (0..difficulty.code_length)
-            .map(|_| *colors.choose(&mut rng).unwrap())
-            .collect();
+        Self::with_seed(difficulty, rand::random())
+    }
+
+    /// Create a game whose secret is fully determined by `seed`. The seed
+    /// drives ChaCha8, and [`Game::with_rng`] turns its raw output into pegs
+    /// itself rather than through `rand`'s sampling helpers, so a seed deals
+    /// the same secret on every build.
+    pub fn with_seed(difficulty: Difficulty, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        Game {
+            seed: Some(seed),
+            ..Self::with_rng(difficulty, &mut rng)
+        }
+    }
+
+    /// Create a game drawing its secret from `rng`, one `next_u32` per peg
+    /// (and now and then another, to keep every peg equally likely).
+    pub fn with_rng<R: Rng + ?Sized>(difficulty: Difficulty, rng: &mut R) -> Self {
+        let palette = difficulty.palette();
+        let pegs = palette.pegs();
+        let secret = match difficulty.repeats {
+            Repeats::Allowed => (0..difficulty.code_length)
+                .map(|_| pegs[index_below(rng, pegs.len())])
+                .collect(),
+            Repeats::UniqueSecret | Repeats::Forbidden => {
+                // A Fisher–Yates shuffle, stopped once every slot is filled.
+                let mut pegs = pegs;
+                for slot in 0..difficulty.code_length {
+                    let pick = slot + index_below(rng, pegs.len() - slot);
+                    pegs.swap(slot, pick);
+                }
+                pegs.truncate(difficulty.code_length);
+                pegs
+            }
+        };
 
+        Game {
+            secret_code: Code::from_parts(secret, palette),
+            difficulty,
+            hints_used: 0,
+            seed: None,
+            started: SystemTime::now(),
+            state: GameState::InProgress,
+            history: Vec::new(),
+        }
+    }
+
+    /// Rebuild a game from its pieces, e.g. from a save file. The state
+    /// follows from the recorded feedback; the caller is responsible for
+    /// checking that the pieces fit together.
+    pub fn restore(
+        difficulty: Difficulty,
+        secret_code: Code,
+        seed: Option<u64>,
+        hints_used: usize,
+        started: SystemTime,
+        history: Vec<TurnRecord>,
+    ) -> Self {
+        let won = history
+            .iter()
+            .any(|turn| turn.feedback.exact_matches == difficulty.code_length);
+        let state = if won {
+            GameState::Won
+        } else if history.len() >= difficulty.max_attempts {
+            GameState::Lost
+        } else {
+            GameState::InProgress
+        };
         Game {
             secret_code,
-            attempts: 0,
             difficulty,
+            hints_used,
+            seed,
+            started,
+            state,
+            history,
         }
     }
 
     /// Validate a guess string against the current difficulty
-    pub fn validate_guess(&self, guess: &str) -> Result<Vec<char>, String> {
-        if guess.len() != self.difficulty.code_length {
-            return Err(format!(
-                "Invalid length! Please enter exactly {} colors.",
-                self.difficulty.code_length
-            ));
-        }
+    pub fn validate_guess(&self, guess: &str) -> Result<Code, GuessError> {
+        self.difficulty.parse_guess(guess)
+    }
+
+    /// Calculate feedback for a guess, without playing it. Fails if the guess
+    /// doesn't fit the difficulty.
+    pub fn get_feedback(&self, guess: &Code) -> Result<Feedback, GuessError> {
+        self.difficulty.check_guess(guess)?;
+        self.secret_code.score(guess)
+    }
 
-        let guess_upper: Vec<char> = guess.to_uppercase().chars().collect();
-        let allowed = self.difficulty.colors();
+    /// Register a guess attempt and return its feedback plus whether it won.
+    /// Fails once the game is over, or if the guess doesn't fit the difficulty.
+    pub fn submit_guess(&mut self, guess: &Code) -> Result<(Feedback, bool), GuessError> {
+        match self.state {
+            GameState::InProgress => {}
+            GameState::Lost => return Err(GuessError::OutOfAttempts),
+            GameState::Won | GameState::Abandoned => return Err(GuessError::GameOver),
+        }
+        let feedback = self.get_feedback(guess)?;
+        self.history.push(TurnRecord {
+            guess: guess.clone(),
+            feedback,
+            timestamp: SystemTime::now(),
+        });
+        let won = feedback.exact_matches == self.difficulty.code_length;
+        if won {
+            self.state = GameState::Won;
+        } else if self.history.len() >= self.difficulty.max_attempts {
+            self.state = GameState::Lost;
+        }
+        Ok((feedback, won))
+    }
 
-        for &ch in &guess_upper {
-            if !allowed.contains(&ch) {
-                return Err(format!(
-                    "Invalid color '{}'. Use only: {}",
-                    ch,
-                    allowed.iter().collect::<String>()
-                ));
-            }
+    /// Give up on a game in progress. Does nothing once the game is over.
+    /// Either way the secret can be read from [`Game::secret`] afterwards.
+    pub fn abandon(&mut self) {
+        if self.state == GameState::InProgress {
+            self.state = GameState::Abandoned;
         }
+    }
 
-        Ok(guess_upper)
+    /// The rules this game is played under.
+    pub fn difficulty(&self) -> &Difficulty {
+        &self.difficulty
     }
 
-    /// Calculate feedback for a guess
-    pub fn get_feedback(&self, guess: &[char]) -> Feedback {
-        let mut exact_matches = 0;
-        let mut secret_remaining = Vec::new();
-        let mut guess_remaining = Vec::new();
+    /// Solver hints asked for so far; see [`Game::record_hint`].
+    pub fn hints_used(&self) -> usize {
+        self.hints_used
+    }
 
-        // First pass: find exact matches
-        for i in 0..self.secret_code.len() {
-            if guess[i] == self.secret_code[i] {
-                exact_matches += 1;
-            } else {
-                secret_remaining.push(self.secret_code[i]);
-                guess_remaining.push(guess[i]);
-            }
-        }
+    /// The seed the secret was generated from, revealed like the secret only
+    /// once the game is over. `None` too if the secret didn't come from one.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed.filter(|_| self.is_over())
+    }
 
-        // Second pass: find color matches (right color, wrong position)
-        let mut color_matches = 0;
-        for &color in &guess_remaining {
-            if let Some(pos) = secret_remaining.iter().position(|&c| c == color) {
-                color_matches += 1;
-                secret_remaining.remove(pos);
-            }
+    /// Where the game stands.
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /// Whether the game has been won, lost or abandoned.
+    pub fn is_over(&self) -> bool {
+        self.state != GameState::InProgress
+    }
+
+    /// How the game ended, or `None` while it is still being played.
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self.state {
+            GameState::InProgress => None,
+            GameState::Won => Some(Outcome::Won),
+            GameState::Lost => Some(Outcome::Lost),
+            GameState::Abandoned => Some(Outcome::Abandoned),
         }
+    }
+
+    /// The secret, revealed only once the game is over.
+    pub fn secret(&self) -> Option<&Code> {
+        self.is_over().then_some(&self.secret_code)
+    }
+
+    /// Guesses submitted so far.
+    pub fn attempts(&self) -> usize {
+        self.history.len()
+    }
 
-        Feedback {
-            exact_matches,
-            color_matches,
+    /// Guesses still allowed; zero once the game is over.
+    pub fn remaining_attempts(&self) -> usize {
+        if self.is_over() {
+            return 0;
         }
+        self.difficulty.max_attempts.saturating_sub(self.attempts())
     }
 
-    /// Register a guess attempt and return its feedback plus whether it won.
-    pub fn submit_guess(&mut self, guess: &[char]) -> (Feedback, bool) {
-        self.attempts += 1;
-        let feedback = self.get_feedback(guess);
-        let won = feedback.exact_matches == self.difficulty.code_length;
-        (feedback, won)
+    /// Every turn played so far, oldest first.
+    pub fn history(&self) -> &[TurnRecord] {
+        &self.history
+    }
+
+    /// Time from the start of the game to its latest guess.
+    pub fn elapsed(&self) -> Duration {
+        self.history
+            .last()
+            .and_then(|turn| turn.timestamp.duration_since(self.started).ok())
+            .unwrap_or_default()
+    }
+
+    /// Record that the player asked the solver for a hint.
+    pub fn record_hint(&mut self) {
+        self.hints_used += 1;
+    }
+
+    /// Whether the player has won (or is playing) without any hints.
+    pub fn is_unaided(&self) -> bool {
+        self.hints_used == 0
     }
 }
 
+/// A uniformly drawn index below `n`, taken straight from `rng.next_u32()`.
+/// Draws that would favour the low indexes are rejected and drawn again.
+fn index_below<R: Rng + ?Sized>(rng: &mut R, n: usize) -> usize {
+    let n = n as u64;
+    let limit = (1u64 << 32) / n * n;
+    loop {
+        let draw = u64::from(rng.next_u32());
+        if draw < limit {
+            return (draw % n) as usize;
+        }
+    }
+}
+
+/// Test shorthand for a game at `difficulty` with the secret spelled by
+/// `secret` and nothing played yet.
+#[cfg(test)]
+pub(crate) fn game_with_secret(difficulty: Difficulty, secret: &str) -> Game {
+    let secret = crate::code::code(secret);
+    Game::restore(difficulty, secret, None, 0, SystemTime::now(), Vec::new())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::code::code;
+
+    #[test]
+    fn test_feedback_refuses_codes_from_other_difficulties() {
+        let game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        assert_eq!(
+            game.get_feedback(&code("RGBYM")),
+            Err(GuessError::WrongLength { expected: 4, actual: 5 })
+        );
+        assert!(matches!(
+            game.get_feedback(&code("RGBW")),
+            Err(GuessError::IllegalSymbol { symbol: 'W', .. })
+        ));
+    }
 
     #[test]
     fn test_feedback_all_exact() {
-        let game = Game {
-            secret_code: vec!['R', 'G', 'B', 'Y'],
-            attempts: 0,
-            difficulty: Difficulty::CLASSIC,
-        };
-        let feedback = game.get_feedback(&['R', 'G', 'B', 'Y']);
+        let game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        let feedback = game.get_feedback(&code("RGBY")).unwrap();
         assert_eq!(feedback.exact_matches, 4);
         assert_eq!(feedback.color_matches, 0);
     }
 
     #[test]
     fn test_feedback_no_matches() {
-        let game = Game {
-            secret_code: vec!['R', 'G', 'B', 'Y'],
-            attempts: 0,
-            difficulty: Difficulty::CLASSIC,
-        };
-        let feedback = game.get_feedback(&['M', 'M', 'C', 'C']);
+        let game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        let feedback = game.get_feedback(&code("MMCC")).unwrap();
         assert_eq!(feedback.exact_matches, 0);
         assert_eq!(feedback.color_matches, 0);
     }
 
     #[test]
     fn test_feedback_color_matches() {
-        let game = Game {
-            secret_code: vec!['R', 'G', 'B', 'Y'],
-            attempts: 0,
-            difficulty: Difficulty::CLASSIC,
-        };
-        let feedback = game.get_feedback(&['Y', 'B', 'G', 'R']);
+        let game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        let feedback = game.get_feedback(&code("YBGR")).unwrap();
         assert_eq!(feedback.exact_matches, 0);
         assert_eq!(feedback.color_matches, 4);
     }
 
     #[test]
     fn test_feedback_mixed() {
-        let game = Game {
-            secret_code: vec!['R', 'G', 'B', 'Y'],
-            attempts: 0,
-            difficulty: Difficulty::CLASSIC,
-        };
-        let feedback = game.get_feedback(&['R', 'B', 'Y', 'M']);
+        let game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        let feedback = game.get_feedback(&code("RBYM")).unwrap();
         assert_eq!(feedback.exact_matches, 1); // R in position 0
         assert_eq!(feedback.color_matches, 2); // B and Y in wrong positions
     }
@@ -224,12 +753,8 @@ mod tests {
 
     #[test]
     fn test_easy_feedback_four_slots() {
-        let game = Game {
-            secret_code: vec!['R', 'G', 'B', 'Y'],
-            attempts: 0,
-            difficulty: Difficulty::EASY,
-        };
-        let feedback = game.get_feedback(&['G', 'R', 'B', 'Y']);
+        let game = game_with_secret(Difficulty::EASY, "RGBY");
+        let feedback = game.get_feedback(&code("GRBY")).unwrap();
         assert_eq!(feedback.exact_matches, 2); // B, Y in place
         assert_eq!(feedback.color_matches, 2); // R, G swapped
     }
@@ -243,22 +768,307 @@ mod tests {
 
     #[test]
     fn test_hard_feedback_five_slots() {
-        let game = Game {
-            secret_code: vec!['R', 'G', 'B', 'Y', 'M'],
-            attempts: 0,
-            difficulty: Difficulty::HARD,
-        };
+        let game = game_with_secret(Difficulty::HARD, "RGBYM");
         // All five exact
-        let all = game.get_feedback(&['R', 'G', 'B', 'Y', 'M']);
+        let all = game.get_feedback(&code("RGBYM")).unwrap();
         assert_eq!(all.exact_matches, 5);
         assert_eq!(all.color_matches, 0);
 
         // One exact (R), two color matches (G, B shifted), two absent (C, C)
-        let mixed = game.get_feedback(&['R', 'B', 'G', 'C', 'C']);
+        let mixed = game.get_feedback(&code("RBGCC")).unwrap();
         assert_eq!(mixed.exact_matches, 1); // R in position 0
         assert_eq!(mixed.color_matches, 2); // G and B present, wrong spot
     }
 
+    #[test]
+    fn test_history_records_turns_in_order() {
+        let mut game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        assert!(game.history().is_empty());
+
+        game.submit_guess(&code("RRGG")).unwrap();
+        game.submit_guess(&code("RGBY")).unwrap();
+
+        let history = game.history();
+        assert_eq!(history.len(), game.attempts());
+        assert_eq!(history[0].guess, code("RRGG"));
+        assert_eq!(history[0].feedback, game.get_feedback(&code("RRGG")).unwrap());
+        assert!(history[0].timestamp <= history[1].timestamp);
+        assert_eq!(history[1].feedback.exact_matches, 4);
+    }
+
+    #[test]
+    fn test_elapsed_runs_to_the_latest_guess() {
+        let mut game = Game::new(Difficulty::CLASSIC);
+        assert_eq!(game.elapsed(), Duration::ZERO);
+
+        game.started -= Duration::from_secs(90);
+        game.submit_guess(&code("RRGG")).unwrap();
+        assert!(game.elapsed() >= Duration::from_secs(90));
+        assert!(game.elapsed() < Duration::from_secs(100));
+    }
+
+    #[test]
+    fn test_hints_are_counted() {
+        let mut game = Game::new(Difficulty::CLASSIC);
+        assert!(game.is_unaided());
+        game.record_hint();
+        game.record_hint();
+        assert_eq!(game.hints_used, 2);
+        assert!(!game.is_unaided());
+    }
+
+    #[test]
+    fn test_custom_difficulty() {
+        let custom = Difficulty::custom(8, 10, 15).unwrap();
+        assert_eq!(custom.code_length, 8);
+        assert_eq!(custom.colors().len(), 10);
+        assert_eq!(custom.code_space(), Some(10usize.pow(8)));
+
+        let game = Game::new(custom);
+        assert_eq!(game.secret_code.len(), 8);
+        assert!(game.validate_guess("RGBYMCWO").is_ok());
+        assert!(game.validate_guess("RGBYMCWN").is_err()); // N is the 11th color
+
+        assert!(Difficulty::custom(2, 2, 1).is_ok());
+        assert!(Difficulty::custom(MAX_CODE_LENGTH, COLORS.len(), MAX_ATTEMPTS).is_ok());
+    }
+
+    #[test]
+    fn test_custom_difficulty_rejects_impossible_settings() {
+        assert_eq!(Difficulty::custom(1, 6, 10), Err(DifficultyError::CodeLength(1)));
+        assert_eq!(
+            Difficulty::custom(MAX_CODE_LENGTH + 1, 6, 10),
+            Err(DifficultyError::CodeLength(MAX_CODE_LENGTH + 1))
+        );
+        assert_eq!(Difficulty::custom(4, 1, 10), Err(DifficultyError::NumColors(1)));
+        assert_eq!(
+            Difficulty::custom(4, COLORS.len() + 1, 10),
+            Err(DifficultyError::NumColors(COLORS.len() + 1))
+        );
+        assert_eq!(Difficulty::custom(4, 6, 0), Err(DifficultyError::MaxAttempts(0)));
+    }
+
+    #[test]
+    fn test_unique_secret_never_repeats() {
+        let difficulty = Difficulty::CLASSIC.with_repeats(Repeats::UniqueSecret).unwrap();
+        for _ in 0..50 {
+            let game = Game::new(difficulty);
+            assert_eq!(game.secret_code.find_repeat(), None);
+        }
+
+        // Guesses may still repeat unless repeats are forbidden outright.
+        assert!(Game::new(difficulty).validate_guess("RRGG").is_ok());
+    }
+
+    #[test]
+    fn test_forbidden_repeats_reject_guesses() {
+        let difficulty = Difficulty::CLASSIC.with_repeats(Repeats::Forbidden).unwrap();
+        let game = Game::new(difficulty);
+        assert!(game.validate_guess("RGBY").is_ok());
+        assert!(game.validate_guess("RGBR").is_err());
+    }
+
+    #[test]
+    fn test_guess_errors_point_at_the_problem() {
+        let game = Game::new(Difficulty::CLASSIC);
+        assert_eq!(
+            game.validate_guess("RGB"),
+            Err(GuessError::WrongLength { expected: 4, actual: 3 })
+        );
+        let error = game.validate_guess("RgXB").unwrap_err();
+        assert_eq!(
+            error,
+            GuessError::IllegalSymbol {
+                symbol: 'X',
+                position: 2,
+                palette: Difficulty::CLASSIC.palette(),
+            }
+        );
+        assert_eq!(error.to_string(), "Invalid color 'X' in slot 3. Use only: RGBYMC");
+        let blanks = Game::new(Difficulty::EASY.with_blanks(true).unwrap());
+        let error = blanks.validate_guess("RGBM").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid color 'M' in slot 4. Use only: RGBY_");
+
+        let difficulty = Difficulty::CLASSIC.with_repeats(Repeats::Forbidden).unwrap();
+        let error = Game::new(difficulty).validate_guess("RGBg").unwrap_err();
+        assert_eq!(error, GuessError::Duplicate { symbol: 'G', first: 1, position: 3 });
+        assert_eq!(
+            error.to_string(),
+            "Color 'G' is used again in slot 4. Each color may appear only once."
+        );
+    }
+
+    #[test]
+    fn test_finished_games_reject_guesses() {
+        let mut game = game_with_secret(Difficulty::EASY, "RGBY");
+        assert!(game.submit_guess(&code("RGBY")).unwrap().1);
+        assert_eq!(game.submit_guess(&code("RGBY")), Err(GuessError::GameOver));
+        assert_eq!(game.attempts(), 1);
+
+        let mut game = game_with_secret(Difficulty::EASY, "RGBY");
+        for _ in 0..Difficulty::EASY.max_attempts {
+            assert!(!game.submit_guess(&code("RRRR")).unwrap().1);
+        }
+        assert_eq!(game.submit_guess(&code("RGBY")), Err(GuessError::OutOfAttempts));
+        assert_eq!(game.history().len(), Difficulty::EASY.max_attempts);
+    }
+
+    #[test]
+    fn test_state_machine() {
+        let mut game = game_with_secret(Difficulty::CLASSIC, "RGBY");
+        assert_eq!(game.state(), GameState::InProgress);
+        assert_eq!(game.outcome(), None);
+        assert_eq!(game.secret(), None);
+        assert_eq!(game.remaining_attempts(), 10);
+
+        game.submit_guess(&code("RRGG")).unwrap();
+        assert_eq!(game.remaining_attempts(), 9);
+        assert_eq!(game.secret(), None);
+
+        game.abandon();
+        assert_eq!(game.outcome(), Some(Outcome::Abandoned));
+        assert_eq!(game.secret(), Some(&code("RGBY")));
+        assert_eq!(game.remaining_attempts(), 0);
+        assert_eq!(game.submit_guess(&code("RGBY")), Err(GuessError::GameOver));
+
+        // Restored games pick up where the recorded feedback left them, and
+        // abandoning a finished game doesn't change how it ended.
+        let mut history = game.history().to_vec();
+        history.push(TurnRecord {
+            guess: code("RGBY"),
+            feedback: Feedback { exact_matches: 4, color_matches: 0 },
+            timestamp: SystemTime::now(),
+        });
+        let secret = code("RGBY");
+        let mut won = Game::restore(Difficulty::CLASSIC, secret, None, 0, game.started, history);
+        won.abandon();
+        assert_eq!(won.state(), GameState::Won);
+    }
+
+    #[test]
+    fn test_unique_colors_need_enough_colors() {
+        assert_eq!(
+            Difficulty::custom(5, 4, 10).unwrap().with_repeats(Repeats::Forbidden),
+            Err(DifficultyError::NotEnoughUniqueColors {
+                code_length: 5,
+                num_colors: 4,
+            })
+        );
+        assert!(Difficulty::EASY.with_repeats(Repeats::Forbidden).is_ok());
+    }
+
+    #[test]
+    fn test_blanks_count_toward_unique_colors() {
+        let short = Difficulty::custom(3, 2, 10).unwrap();
+        let error = DifficultyError::NotEnoughUniqueColors {
+            code_length: 3,
+            num_colors: 2,
+        };
+
+        // Blanks first, then the rule: the blank makes up the third symbol.
+        let blanks = short.with_blanks(true).unwrap();
+        let forbidden = blanks.with_repeats(Repeats::Forbidden).unwrap();
+        assert_eq!(forbidden.with_blanks(false), Err(error));
+
+        // The rule first has only two colors to work with.
+        assert_eq!(short.with_repeats(Repeats::Forbidden), Err(error));
+        let unique = blanks.with_repeats(Repeats::UniqueSecret).unwrap();
+        assert_eq!(unique.with_blanks(false), Err(error));
+    }
+
+    #[test]
+    fn test_code_space_respects_repeats() {
+        assert_eq!(Difficulty::CLASSIC.code_space(), Some(1296));
+        let unique = Difficulty::CLASSIC.with_repeats(Repeats::UniqueSecret).unwrap();
+        assert_eq!(unique.code_space(), Some(360));
+        assert_eq!(unique.guess_space(), Some(1296));
+        let forbidden = Difficulty::CLASSIC.with_repeats(Repeats::Forbidden).unwrap();
+        assert_eq!(forbidden.guess_space(), Some(360));
+    }
+
+    #[test]
+    fn test_blanks_extend_the_palette() {
+        assert!(!Difficulty::CLASSIC.symbols().contains(&BLANK));
+
+        let difficulty = Difficulty::CLASSIC.with_blanks(true).unwrap();
+        assert_eq!(difficulty.symbols(), vec!['R', 'G', 'B', 'Y', 'M', 'C', BLANK]);
+        assert_eq!(difficulty.code_space(), Some(7usize.pow(4)));
+
+        let game = Game::new(difficulty);
+        assert_eq!(game.validate_guess("R_G_"), Ok(code("R_G_")));
+        assert!(Game::new(Difficulty::CLASSIC).validate_guess("R_G_").is_err());
+    }
+
+    #[test]
+    fn test_blanks_score_like_colors() {
+        let game = game_with_secret(Difficulty::CLASSIC.with_blanks(true).unwrap(), "R__G");
+        let feedback = game.get_feedback(&code("__RG")).unwrap();
+        assert_eq!(feedback.exact_matches, 2); // blank in slot 1, G in slot 3
+        assert_eq!(feedback.color_matches, 2); // the other blank and R
+    }
+
+    #[test]
+    fn test_seeded_games_are_reproducible() {
+        for difficulty in Difficulty::ALL {
+            let first = Game::with_seed(difficulty, 42);
+            let second = Game::with_seed(difficulty, 42);
+            assert_eq!(first.secret_code, second.secret_code);
+            assert_eq!(first.seed, Some(42));
+        }
+
+        // The seed gives the secret away, so it stays hidden just as long.
+        let mut game = Game::with_seed(Difficulty::CLASSIC, 42);
+        assert_eq!(game.seed(), None);
+        game.abandon();
+        assert_eq!(game.seed(), Some(42));
+
+        let secrets: Vec<Code> = (0..20)
+            .map(|seed| Game::with_seed(Difficulty::CLASSIC, seed).secret_code)
+            .collect();
+        assert!(secrets.iter().any(|secret| secret != &secrets[0]));
+    }
+
+    #[test]
+    fn test_seeds_deal_pinned_secrets() {
+        // Changing these means every shared seed and daily puzzle changes too.
+        let secrets: Vec<Code> = Difficulty::ALL
+            .into_iter()
+            .map(|difficulty| Game::with_seed(difficulty, 42).secret_code)
+            .collect();
+        assert_eq!(secrets, [code("GGRB"), code("YYMR"), code("YYMRB")]);
+
+        let unique = Difficulty::CLASSIC.with_repeats(Repeats::UniqueSecret).unwrap();
+        assert_eq!(Game::with_seed(unique, 42).secret_code, code("YGBR"));
+    }
+
+    #[test]
+    fn test_with_rng_uses_the_given_rng() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let game = Game::with_rng(Difficulty::HARD, &mut rng);
+        assert_eq!(game.seed, None);
+        assert_eq!(game.secret_code, Game::with_seed(Difficulty::HARD, 7).secret_code);
+
+        assert!(Game::new(Difficulty::CLASSIC).seed.is_some());
+    }
+
+    #[test]
+    fn test_presets_are_valid() {
+        for difficulty in Difficulty::ALL {
+            assert_eq!(difficulty.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_display_names_every_rule() {
+        assert_eq!(
+            Difficulty::CLASSIC.to_string(),
+            "Classic · 4 slots · 6 colors · 10 guesses · colors may repeat"
+        );
+        let variant = Difficulty::CLASSIC.with_blanks(true).unwrap();
+        assert_ne!(variant.to_string(), Difficulty::CLASSIC.to_string());
+        assert!(variant.to_string().ends_with(" · blanks allowed"));
+    }
+
     #[test]
     fn test_difficulty_color_palettes() {
         assert_eq!(Difficulty::EASY.colors(), &['R', 'G', 'B', 'Y']);
@@ -0,0 +1,225 @@
+//! Player profiles and the local high-score table — no terminal I/O lives here.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::storage;
+
+/// First line of the leaderboard file; bump the version when the format changes.
+const HEADER: &str = "ciphermind-leaderboard 1";
+
+/// Entries kept per difficulty.
+pub const MAX_ENTRIES: usize = 10;
+
+/// Longest allowed player name.
+pub const MAX_NAME_LENGTH: usize = 20;
+
+/// One winning game on the board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The profile that won.
+    pub player: String,
+    /// The preset's name.
+    pub difficulty: String,
+    /// Guesses the win took.
+    pub guesses: usize,
+    /// From the start of the game to the winning guess.
+    pub time: Duration,
+}
+
+/// Every difficulty's best wins, plus the known player profiles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Leaderboard {
+    /// Profile names, in the order they were created.
+    pub players: Vec<String>,
+    /// The kept wins of every difficulty (see [`Leaderboard::top`]).
+    pub entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+    /// Read the leaderboard from `path`; a missing file means an empty board.
+    pub fn load(path: &Path) -> io::Result<Leaderboard> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Leaderboard::parse(&contents),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Leaderboard::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Write the leaderboard to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    /// Add a profile if it isn't known yet.
+    pub fn add_player(&mut self, name: &str) {
+        if !self.players.iter().any(|player| player == name) {
+            self.players.push(name.to_string());
+        }
+    }
+
+    /// Record a win, keeping only the best [`MAX_ENTRIES`] for its difficulty.
+    /// Returns the entry's 1-based rank if it made the board.
+    pub fn submit(&mut self, entry: Entry) -> Option<usize> {
+        let difficulty = entry.difficulty.clone();
+        let mut ranked: Vec<Entry> = self
+            .entries
+            .iter()
+            .filter(|e| e.difficulty == difficulty)
+            .cloned()
+            .collect();
+        ranked.push(entry.clone());
+        // Stable sort: an earlier entry keeps its place over a tied newcomer.
+        ranked.sort_by_key(|e| (e.guesses, e.time));
+        ranked.truncate(MAX_ENTRIES);
+
+        let rank = ranked.iter().rposition(|e| *e == entry).map(|i| i + 1);
+        self.entries.retain(|e| e.difficulty != difficulty);
+        self.entries.extend(ranked);
+        rank
+    }
+
+    /// The board for one difficulty, best first.
+    pub fn top(&self, difficulty: &str) -> Vec<&Entry> {
+        let mut ranked: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|e| e.difficulty == difficulty)
+            .collect();
+        ranked.sort_by_key(|e| (e.guesses, e.time));
+        ranked
+    }
+
+    /// `player NAME` lines, then one tab-separated line per entry.
+    fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for player in &self.players {
+            text.push_str(&format!("player\t{}\n", player));
+        }
+        for e in &self.entries {
+            text.push_str(&format!(
+                "entry\t{}\t{}\t{}\t{}\n",
+                e.difficulty,
+                e.guesses,
+                e.time.as_millis(),
+                e.player
+            ));
+        }
+        text
+    }
+
+    fn parse(contents: &str) -> io::Result<Leaderboard> {
+        let invalid = |what: &str| io::Error::new(io::ErrorKind::InvalidData, what.to_string());
+
+        let mut lines = contents.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("not a CipherMind leaderboard file"));
+        }
+
+        let mut board = Leaderboard::default();
+        for line in lines.filter(|line| !line.is_empty()) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields[..] {
+                ["player", name] => board.add_player(name),
+                ["entry", difficulty, guesses, millis, player] => board.entries.push(Entry {
+                    player: player.to_string(),
+                    difficulty: difficulty.to_string(),
+                    guesses: guesses.parse().map_err(|_| invalid(line))?,
+                    time: Duration::from_millis(millis.parse().map_err(|_| invalid(line))?),
+                }),
+                _ => return Err(invalid(line)),
+            }
+        }
+        Ok(board)
+    }
+}
+
+/// Check a new profile name, returning it trimmed.
+pub fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Please enter a name.".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!("Names can be at most {} characters.", MAX_NAME_LENGTH));
+    }
+    if name.chars().any(char::is_control) {
+        return Err("Names can't contain tabs or control characters.".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// Where profiles and the leaderboard are kept.
+pub fn default_path() -> PathBuf {
+    storage::data_dir().join("leaderboard.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(player: &str, guesses: usize, secs: u64) -> Entry {
+        Entry {
+            player: player.to_string(),
+            difficulty: "Classic".to_string(),
+            guesses,
+            time: Duration::from_secs(secs),
+        }
+    }
+
+    #[test]
+    fn test_ranks_by_guesses_then_time() {
+        let mut board = Leaderboard::default();
+        assert_eq!(board.submit(entry("ana", 5, 60)), Some(1));
+        assert_eq!(board.submit(entry("bo", 4, 300)), Some(1));
+        assert_eq!(board.submit(entry("cy", 5, 30)), Some(2));
+
+        let names: Vec<&str> = board.top("Classic").iter().map(|e| e.player.as_str()).collect();
+        assert_eq!(names, ["bo", "cy", "ana"]);
+        assert!(board.top("Hard").is_empty());
+    }
+
+    #[test]
+    fn test_keeps_only_the_best() {
+        let mut board = Leaderboard::default();
+        for i in 0..MAX_ENTRIES {
+            board.submit(entry("ana", 3, i as u64));
+        }
+        assert_eq!(board.submit(entry("bo", 9, 1)), None);
+        assert_eq!(board.submit(entry("bo", 2, 500)), Some(1));
+        assert_eq!(board.top("Classic").len(), MAX_ENTRIES);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("ciphermind-leaderboard-test-{}", std::process::id()))
+            .join("leaderboard.txt");
+        assert_eq!(Leaderboard::load(&path).unwrap(), Leaderboard::default());
+
+        let mut board = Leaderboard::default();
+        board.add_player("ana");
+        board.add_player("Bo Li");
+        board.add_player("ana");
+        board.submit(entry("Bo Li", 4, 75));
+        board.save(&path).unwrap();
+
+        let loaded = Leaderboard::load(&path).unwrap();
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.players, ["ana", "Bo Li"]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert_eq!(validate_name("  ana "), Ok("ana".to_string()));
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name("a\tb").is_err());
+        assert!(validate_name(&"x".repeat(MAX_NAME_LENGTH + 1)).is_err());
+    }
+}
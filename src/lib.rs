@@ -0,0 +1,44 @@
+//! The CipherMind engine: rules, scoring, solvers and saved-data formats.
+//!
+//! Everything here is free of terminal I/O, so bots, servers and analysis
+//! tools can link the same scoring code the game uses. The interactive game
+//! itself (menus, rendering, the full-screen board) lives in the binary.
+//!
+//! ```
+//! use ciphermind::code::Code;
+//! use ciphermind::game::{Difficulty, Game, Outcome};
+//! use ciphermind::solver;
+//!
+//! // Score a guess against a secret.
+//! let secret = Code::parse("RGBY", &Difficulty::CLASSIC).unwrap();
+//! let guess = Code::parse("RBGG", &Difficulty::CLASSIC).unwrap();
+//! let feedback = secret.score(&guess).unwrap();
+//! assert_eq!((feedback.exact_matches, feedback.color_matches), (1, 2));
+//!
+//! // Play a reproducible game...
+//! let mut game = Game::with_seed(Difficulty::CLASSIC, 42);
+//! let guess = game.validate_guess("RGBY").unwrap();
+//! let (_, won) = game.submit_guess(&guess).unwrap();
+//! assert_eq!(won, game.outcome() == Some(Outcome::Won));
+//! assert_eq!(game.remaining_attempts(), if won { 0 } else { 9 });
+//!
+//! // ...give up to see the secret, and let the Knuth solver crack it.
+//! game.abandon();
+//! let secret = game.secret().unwrap();
+//! let turns = solver::solve(game.difficulty(), secret).unwrap();
+//! assert!(turns.len() <= 5);
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod analysis;
+pub mod code;
+pub mod daily;
+pub mod game;
+pub mod leaderboard;
+pub mod record;
+pub mod save;
+pub mod solver;
+pub mod stats;
+pub mod storage;
+pub mod table;
@@ -1,85 +1,788 @@
-mod game;
+mod board;
 mod ui;
 
-use std::io::{self, Write};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
 
-use game::Game;
+use ciphermind::game::{Difficulty, Game, Mode, Outcome};
+use ciphermind::leaderboard::{Entry, Leaderboard};
+use ciphermind::stats::Stats;
+use ciphermind::{analysis, daily, leaderboard, record, save, solver, stats};
+use ui::{errln, outln, FeedbackStyle, MenuChoice, PlayAgain, RenderStyle, Selection};
+
+/// What to do, chosen by the subcommand.
+#[derive(Debug, Clone, Default, PartialEq)]
+enum Command {
+    /// Open the interactive menu.
+    #[default]
+    Menu,
+    /// Play codebreaker games straight away.
+    Play,
+    /// Think of a code and let the solver crack it.
+    Solve,
+    /// Play today's daily challenge.
+    Daily,
+    /// Show the stats screen.
+    Stats,
+    /// Step through a game record.
+    Replay(PathBuf),
+    /// Print the usage and exit.
+    Help,
+}
+
+/// Where a codebreaker game's secret came from. Only fresh random secrets
+/// can earn a place on the leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Origin {
+    /// A secret nobody has seen.
+    Random,
+    /// A secret fixed by `--seed`; seeds are shown at every game's end.
+    Seeded,
+    /// A saved game, whose secret may have been revealed by quitting it.
+    Resumed,
+    /// Today's daily challenge, shared by every player.
+    Daily,
+}
+
+/// Command-line options.
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    command: Command,
+    /// The preset for `play`, `solve` and `daily` (Classic when not given).
+    difficulty: Option<Difficulty>,
+    /// Fixes the secret of the first game only.
+    seed: Option<u64>,
+    /// A save file to resume before anything else.
+    resume: Option<PathBuf>,
+    /// How pegs are drawn; detected from the environment when not given.
+    style: Option<RenderStyle>,
+    /// How feedback is shown.
+    feedback: Option<FeedbackStyle>,
+}
+
+const HELP: &str = "\
+CipherMind — the ultimate code-breaking challenge
+
+Usage: ciphermind [COMMAND] [OPTIONS]
+
+Commands:
+  (none)             Open the interactive menu
+  play               Play codebreaker games straight away
+  solve              Think of a code and let the solver crack it
+  daily              Play today's daily challenge
+  stats              Show your stats
+  replay FILE        Step through a recorded game
+  help               Show this help
+
+Options:
+  --difficulty NAME  easy, classic or hard for play, solve and daily (default classic)
+  --seed N           Fix the secret of the first game (play and the menu)
+  --resume FILE      Resume a saved game first (play and the menu)
+  --style STYLE      Draw pegs as color, letters, shapes or ascii
+  --no-color         Same as --style ascii
+  --feedback FORM    Show feedback as text, pegs or both
+  -h, --help         Show this help";
 
 /// Main game loop
 fn main() {
+    let mut options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            ui::set_style(RenderStyle::detect());
+            errln!("❌ {}", error);
+            process::exit(2);
+        }
+    };
+
+    ui::set_style(options.style.unwrap_or_else(RenderStyle::detect));
+    if let Some(feedback) = options.feedback {
+        ui::set_feedback_style(feedback);
+    }
+    let difficulty = options.difficulty.unwrap_or(Difficulty::CLASSIC);
+
+    match options.command.clone() {
+        Command::Help => outln!("{}", HELP),
+        Command::Replay(path) => {
+            if !replay(&path) {
+                process::exit(1);
+            }
+        }
+        Command::Stats => stats_screen(),
+        Command::Solve => {
+            while play_codemaker(difficulty) && play_again() {}
+            ui::print_goodbye();
+        }
+        Command::Daily => {
+            play_daily(difficulty, &choose_player());
+            ui::print_goodbye();
+        }
+        Command::Play => {
+            let player = choose_player();
+            if resume_from_flag(&mut options, &player) {
+                loop {
+                    let (mut game, origin) = new_game(difficulty, options.seed.take());
+                    if !play_codebreaker(&mut game, &player, origin) || !play_again() {
+                        break;
+                    }
+                }
+            }
+            ui::print_goodbye();
+        }
+        Command::Menu => {
+            let player = choose_player();
+            if resume_from_flag(&mut options, &player) {
+                run_menu(&mut options, &player);
+            }
+            ui::print_goodbye();
+        }
+    }
+}
+
+/// Play the game given with `--resume`, if any. Returns `false` if the
+/// player is done afterwards; exits if the save can't be read.
+fn resume_from_flag(options: &mut Options, player: &str) -> bool {
+    let Some(path) = options.resume.take() else {
+        return true;
+    };
+    match save::load(&path) {
+        Ok(mut game) => {
+            // A save is used up once resumed; `save` again to keep it.
+            let _ = fs::remove_file(&path);
+            play_codebreaker(&mut game, player, Origin::Resumed) && play_again()
+        }
+        Err(error) => {
+            errln!("❌ Couldn't resume {}: {}", path.display(), error);
+            process::exit(1);
+        }
+    }
+}
+
+/// A new game, with the secret fixed by `seed` if given.
+fn new_game(difficulty: Difficulty, seed: Option<u64>) -> (Game, Origin) {
+    match seed {
+        Some(seed) => (Game::with_seed(difficulty, seed), Origin::Seeded),
+        None => (Game::new(difficulty), Origin::Random),
+    }
+}
+
+/// The interactive menu, until the player leaves.
+fn run_menu(options: &mut Options, player: &str) {
     loop {
-        let difficulty = ui::select_difficulty();
-        ui::print_welcome(&difficulty);
+        let default_save = save::default_path();
+        if default_save.exists() && ui::confirm_resume() {
+            match save::load(&default_save) {
+                Ok(mut game) => {
+                    // A save is used up once resumed; `save` again to keep it.
+                    let _ = fs::remove_file(&default_save);
+                    if !play_codebreaker(&mut game, player, Origin::Resumed) || !play_again() {
+                        break;
+                    }
+                    continue;
+                }
+                Err(error) => outln!("  ❌ Couldn't resume the saved game: {}", error),
+            }
+        }
 
-        let mut game = Game::new(difficulty);
-        let mut won = false;
+        let mode = match ui::select_mode() {
+            MenuChoice::Play(mode) => mode,
+            MenuChoice::Stats => {
+                stats_screen();
+                continue;
+            }
+            MenuChoice::Leaderboard => {
+                match Leaderboard::load(&leaderboard::default_path()) {
+                    Ok(board) => ui::show_leaderboard(&board),
+                    Err(error) => outln!("  ❌ Couldn't read the leaderboard: {}", error),
+                }
+                continue;
+            }
+        };
+        let selection = ui::select_difficulty(mode == Mode::Codebreaker);
 
-        // Main guessing loop
-        while game.attempts < difficulty.max_attempts && !won {
-            print!("\n🎯 Enter your guess (or 'quit' to exit): ");
-            io::stdout().flush().unwrap();
+        let keep_playing = match (mode, selection) {
+            (Mode::Codebreaker, Selection::Standard(difficulty)) => {
+                let (mut game, origin) = new_game(difficulty, options.seed.take());
+                play_codebreaker(&mut game, player, origin)
+            }
+            (Mode::Codebreaker, Selection::Daily(difficulty)) => play_daily(difficulty, player),
+            (Mode::Codemaker, Selection::Standard(difficulty) | Selection::Daily(difficulty)) => {
+                play_codemaker(difficulty)
+            }
+        };
 
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-            let input = input.trim();
+        // Ask to play again
+        if !keep_playing || !play_again() {
+            break;
+        }
+    }
+}
 
-            // Check for quit
-            if input.eq_ignore_ascii_case("quit") {
-                println!("\n👋 Thanks for playing!");
-                ui::reveal_code(&game.secret_code);
-                return;
+/// Ask whether to play again, showing the stats screen as often as requested.
+fn play_again() -> bool {
+    loop {
+        match ui::play_again() {
+            PlayAgain::Yes => return true,
+            PlayAgain::No => return false,
+            PlayAgain::Stats => stats_screen(),
+        }
+    }
+}
+
+/// Show the stats and offer to reset them.
+fn stats_screen() {
+    let path = stats::default_path();
+    match Stats::load(&path) {
+        Ok(stats) => ui::show_stats(&stats),
+        Err(error) => {
+            outln!("  ❌ Couldn't read your stats: {}", error);
+            return;
+        }
+    }
+
+    if ui::confirm_stats_reset() {
+        match stats::reset(&path) {
+            Ok(()) => outln!("  🧹 Stats cleared."),
+            Err(error) => outln!("  ❌ Couldn't clear your stats: {}", error),
+        }
+    }
+}
+
+/// Add a finished game to the stats file; anything but a win is a loss.
+fn record_stats(game: &Game, won: bool) {
+    let path = stats::default_path();
+    let result = Stats::load(&path).and_then(|mut stats| {
+        stats.record(game.difficulty(), won.then_some(game.attempts()));
+        stats.save(&path)
+    });
+    if let Err(error) = result {
+        outln!("  ⚠️  Couldn't update your stats: {}", error);
+    }
+}
+
+/// Write the game record for a finished (or abandoned) game.
+fn export_record(game: &Game, player: &str, outcome: Outcome) {
+    let path = record::default_path(game);
+    match record::write(game, player, outcome, &path) {
+        Ok(()) => outln!(
+            "📜 Game recorded — replay it with: ciphermind replay {}",
+            path.display()
+        ),
+        Err(error) => outln!("  ⚠️  Couldn't write the game record: {}", error),
+    }
+}
+
+/// Step through a recorded game, re-scoring every guess against the secret.
+/// Returns `false` if the record couldn't be read or a feedback was wrong.
+fn replay(path: &Path) -> bool {
+    let record = match record::load(path) {
+        Ok(record) => record,
+        Err(error) => {
+            errln!("❌ Couldn't read {}: {}", path.display(), error);
+            return false;
+        }
+    };
+
+    ui::show_replay_header(&record);
+    let game = &record.game;
+    let mut mismatches = 0;
+    for (i, turn) in game.history().iter().enumerate() {
+        if i > 0 && !ui::next_turn() {
+            break;
+        }
+        ui::show_guess_result(i + 1, &turn.guess, &turn.feedback);
+        ui::show_turn_time(turn.timestamp.duration_since(game.started).unwrap_or_default());
+        match game.get_feedback(&turn.guess) {
+            Ok(actual) if actual != turn.feedback => {
+                ui::show_feedback_mismatch(&actual);
+                mismatches += 1;
             }
+            _ => {}
+        }
+    }
+    ui::show_replay_summary(&record, mismatches);
+    mismatches == 0
+}
 
-            // Validate and process guess
-            match game.validate_guess(input) {
-                Ok(guess) => {
-                    let (feedback, round_won) = game.submit_guess(&guess);
-                    ui::show_guess_result(game.attempts, &guess, &feedback);
+/// Pick (or create) the profile for this session and remember it.
+fn choose_player() -> String {
+    let path = leaderboard::default_path();
+    let mut board = Leaderboard::load(&path).unwrap_or_else(|error| {
+        outln!("  ⚠️  Couldn't read the leaderboard: {}", error);
+        Leaderboard::default()
+    });
 
-                    // Give encouraging hints while guesses remain
-                    if !round_won && game.attempts < difficulty.max_attempts {
-                        ui::print_hint(&feedback, game.attempts, difficulty.max_attempts);
-                    }
+    let player = ui::select_profile(&board.players);
+    if !board.players.contains(&player) {
+        board.add_player(&player);
+        if let Err(error) = board.save(&path) {
+            outln!("  ⚠️  Couldn't save your profile: {}", error);
+        }
+    }
+    outln!("\n👋 Welcome, {}!", player);
+    player
+}
 
-                    won = round_won;
-                }
-                Err(error) => {
-                    println!("  ❌ {}", error);
-                    continue;
+/// Put an unaided win over a fresh secret at a preset difficulty on the
+/// leaderboard kept at `path`.
+fn record_leaderboard(game: &Game, player: &str, origin: Origin, path: &Path) {
+    let unranked = match origin {
+        Origin::Random => None,
+        Origin::Seeded => Some("Seeded games"),
+        Origin::Resumed => Some("Resumed games"),
+        Origin::Daily => Some("Daily challenges"),
+    };
+    if let Some(kind) = unranked {
+        outln!("  ℹ️  {} don't make the leaderboard — the secret may be known.", kind);
+        return;
+    }
+    if !game.is_unaided() || !Difficulty::ALL.contains(game.difficulty()) {
+        outln!("  ℹ️  Only unaided wins at Easy, Classic or Hard make the leaderboard.");
+        return;
+    }
+
+    let result = Leaderboard::load(path).and_then(|mut board| {
+        let rank = board.submit(Entry {
+            player: player.to_string(),
+            difficulty: game.difficulty().name.to_string(),
+            guesses: game.attempts(),
+            time: game.elapsed(),
+        });
+        board.save(path).map(|()| rank)
+    });
+    match result {
+        Ok(Some(rank)) => outln!(
+            "🏆 #{} on the {} leaderboard ({} in {})!",
+            rank,
+            game.difficulty().name,
+            game.attempts(),
+            ui::format_time(game.elapsed())
+        ),
+        Ok(None) => {}
+        Err(error) => outln!("  ⚠️  Couldn't update the leaderboard: {}", error),
+    }
+}
+
+/// Read the subcommand and flags (flags also take the `--flag=value` form).
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    const TRY_HELP: &str = "Run 'ciphermind --help' for usage.";
+
+    let mut options = Options::default();
+    let mut command = None;
+    let mut help = false;
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            if command.is_some() {
+                return Err(format!("Unexpected argument '{}'. {}", arg, TRY_HELP));
+            }
+            command = Some(match arg.as_str() {
+                "play" => Command::Play,
+                "solve" => Command::Solve,
+                "daily" => Command::Daily,
+                "stats" => Command::Stats,
+                "replay" => {
+                    let path = args.next().ok_or(format!("replay needs a FILE. {}", TRY_HELP))?;
+                    Command::Replay(PathBuf::from(path))
                 }
+                "help" => Command::Help,
+                _ => return Err(format!("Unknown command '{}'. {}", arg, TRY_HELP)),
+            });
+            continue;
+        }
+
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or(format!("{} needs a value. {}", flag, TRY_HELP))
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" if inline.is_none() => help = true,
+            "--difficulty" => {
+                let name = value()?;
+                let difficulty = Difficulty::ALL
+                    .into_iter()
+                    .find(|preset| preset.name.eq_ignore_ascii_case(&name))
+                    .ok_or(format!(
+                        "Unknown difficulty '{}' — use easy, classic or hard.",
+                        name
+                    ))?;
+                options.difficulty = Some(difficulty);
+            }
+            "--seed" => {
+                let seed = value()?;
+                let parsed = seed
+                    .parse()
+                    .map_err(|_| format!("Invalid seed '{}' — use a whole number.", seed))?;
+                options.seed = Some(parsed);
+            }
+            "--resume" => options.resume = Some(PathBuf::from(value()?)),
+            "--style" => {
+                let name = value()?;
+                let style = RenderStyle::from_name(&name).ok_or(format!(
+                    "Unknown style '{}' — use color, letters, shapes or ascii.",
+                    name
+                ))?;
+                options.style = Some(style);
             }
+            "--no-color" if inline.is_none() => options.style = Some(RenderStyle::Ascii),
+            "--feedback" => {
+                let name = value()?;
+                let feedback = FeedbackStyle::from_name(&name).ok_or(format!(
+                    "Unknown feedback style '{}' — use text, pegs or both.",
+                    name
+                ))?;
+                options.feedback = Some(feedback);
+            }
+            _ => return Err(format!("Unknown option '{}'. {}", arg, TRY_HELP)),
         }
+    }
+
+    options.command = if help { Command::Help } else { command.unwrap_or_default() };
+    let command = &options.command;
+    if options.difficulty.is_some()
+        && !matches!(command, Command::Play | Command::Solve | Command::Daily | Command::Help)
+    {
+        return Err(format!("--difficulty only applies to play, solve and daily. {}", TRY_HELP));
+    }
+    if (options.seed.is_some() || options.resume.is_some())
+        && !matches!(command, Command::Menu | Command::Play | Command::Help)
+    {
+        return Err(format!("--seed and --resume only apply to play and the menu. {}", TRY_HELP));
+    }
+    Ok(options)
+}
+
+/// Play today's daily challenge unless it was already attempted, then print
+/// the shareable summary. Returns `false` if the player quit.
+fn play_daily(difficulty: Difficulty, player: &str) -> bool {
+    let date = daily::Date::today();
+    let path = daily::played_path();
 
-        // Game over - show result
-        println!("\n═══════════════════════════════════════════");
-        if won {
-            println!("🎉 CONGRATULATIONS! 🎉");
-            println!(
-                "You cracked the code in {} {}!",
-                game.attempts,
-                if game.attempts == 1 { "guess" } else { "guesses" }
+    match daily::has_played(&path, date, &difficulty) {
+        Ok(true) => {
+            outln!(
+                "\n📅 You've already played the {} daily for {} — come back tomorrow!",
+                difficulty.name, date
             );
+            return true;
+        }
+        Ok(false) => {}
+        Err(error) => outln!("  ⚠️  Couldn't check past daily games: {}", error),
+    }
+    // Recorded up front so quitting doesn't earn a second try.
+    if let Err(error) = daily::record_played(&path, date, &difficulty) {
+        outln!("  ⚠️  Couldn't record today's attempt: {}", error);
+    }
+
+    outln!("\n📅 Daily challenge for {}", date);
+    let mut game = Game::with_seed(difficulty, daily::seed(date, &difficulty));
+    if !play_codebreaker(&mut game, player, Origin::Daily) {
+        return false;
+    }
+
+    let won = game.outcome() == Some(Outcome::Won);
+    ui::show_daily_share(&daily::share_text(date, &difficulty, game.history(), won));
+    true
+}
+
+/// Play one game where `player` cracks the computer's code. Returns `false`
+/// if the player quit.
+fn play_codebreaker(game: &mut Game, player: &str, origin: Origin) -> bool {
+    let difficulty = *game.difficulty();
+
+    // Large custom difficulties are too big to search, so play them unassisted.
+    let mut candidates = solver::all_codes(&difficulty).ok();
+
+    // Catch up with the turns of a resumed game
+    for turn in game.history() {
+        if let Some(candidates) = &mut candidates {
+            solver::narrow(&difficulty, candidates, &turn.guess, &turn.feedback)
+                .expect("the game only holds guesses that fit it");
+        }
+    }
+
+    // Main guessing loop
+    let mut screen = ui::Screen::open(game);
+    while !game.is_over() {
+        let input = screen.read_command(game);
+        let input = input.as_str();
+
+        // Check for quit
+        if input.eq_ignore_ascii_case("quit") {
+            drop(screen);
+            game.abandon();
+            if game.attempts() > 0 {
+                // Quitting reveals the secret, so it can't keep a streak alive.
+                record_stats(game, false);
+                export_record(game, player, Outcome::Abandoned);
+            }
+            let secret = game.secret().expect("the game is over");
+            ui::reveal_code(secret);
+            ui::show_seed(game.seed());
+            if let Ok(reviews) = analysis::review_game(&difficulty, secret, game.history()) {
+                ui::show_analysis(&reviews);
+            }
+            return false;
+        }
+
+        // Save the game to resume later
+        if input.eq_ignore_ascii_case("save") {
+            let path = save::default_path();
+            match save::save(game, &path) {
+                Ok(()) => {
+                    drop(screen);
+                    outln!("  💾 Game saved to {}.", path.display());
+                    outln!("  Resume it from the menu or with --resume {}.", path.display());
+                    return false;
+                }
+                Err(error) => screen.show_error(&format!("Couldn't save the game: {}", error)),
+            }
+            continue;
+        }
+
+        // Ask the solver for the most informative next guess
+        if input.eq_ignore_ascii_case("hint") {
+            let Some(candidates) = &candidates else {
+                screen.show_error("Hints aren't available at this difficulty — too many codes.");
+                continue;
+            };
+            if let Ok(Some((guess, bits))) = solver::best_entropy_guess(&difficulty, candidates) {
+                game.record_hint();
+                screen.show_hint(&guess, bits);
+            }
+            continue;
+        }
 
-            // Add special messages for exceptional performance
-            match game.attempts {
-                1 => println!("🏆 INCREDIBLE! A hole-in-one!"),
-                2..=3 => println!("⭐ AMAZING! You're a master codebreaker!"),
-                4..=6 => println!("✨ EXCELLENT! Great logical thinking!"),
-                _ => println!("👍 Well done!"),
+        // Validate and process guess
+        let turn = game.validate_guess(input).and_then(|guess| {
+            let (feedback, round_won) = game.submit_guess(&guess)?;
+            Ok((guess, feedback, round_won))
+        });
+        match turn {
+            Ok((guess, feedback, _)) => {
+                if let Some(candidates) = &mut candidates {
+                    solver::narrow(&difficulty, candidates, &guess, &feedback)
+                        .expect("the game only accepts guesses that fit it");
+                }
+                screen.show_turn(game, &feedback, candidates.as_deref());
             }
+            Err(error) => screen.show_guess_error(&error),
+        }
+    }
+    screen.close(game);
+
+    // Game over - show result
+    let won = game.outcome() == Some(Outcome::Won);
+    let secret = game.secret().expect("the game is over");
+    record_stats(game, won);
+    if won {
+        record_leaderboard(game, player, origin, &leaderboard::default_path());
+    }
+    export_record(game, player, if won { Outcome::Won } else { Outcome::Lost });
+    outln!("\n═══════════════════════════════════════════");
+    if won {
+        outln!("🎉 CONGRATULATIONS! 🎉");
+        outln!(
+            "You cracked the code in {} {}!",
+            game.attempts(),
+            if game.attempts() == 1 { "guess" } else { "guesses" }
+        );
+
+        // Add special messages for exceptional performance
+        match game.attempts() {
+            1 => outln!("🏆 INCREDIBLE! A hole-in-one!"),
+            2..=3 => outln!("⭐ AMAZING! You're a master codebreaker!"),
+            4..=6 => outln!("✨ EXCELLENT! Great logical thinking!"),
+            _ => outln!("👍 Well done!"),
+        }
+
+        if game.is_unaided() {
+            outln!("🧠 Unaided — no hints used!");
         } else {
-            println!("💥 GAME OVER!");
-            println!("You've used all {} attempts.", difficulty.max_attempts);
-            ui::reveal_code(&game.secret_code);
-            println!("\n🧠 Better luck next time! Each game is a new puzzle.");
+            outln!(
+                "🤝 Assisted with {} {}.",
+                game.hints_used(),
+                if game.hints_used() == 1 { "hint" } else { "hints" }
+            );
         }
-        println!("═══════════════════════════════════════════");
+    } else {
+        outln!("💥 GAME OVER!");
+        outln!("You've used all {} attempts.", difficulty.max_attempts);
+        ui::reveal_code(secret);
+        outln!("\n🧠 Better luck next time! Each game is a new puzzle.");
+    }
+    if let Ok(reviews) = analysis::review_game(&difficulty, secret, game.history()) {
+        ui::show_analysis(&reviews);
+    }
+    if let Ok(solver_guesses) = solver::solve(&difficulty, secret) {
+        ui::show_solver_comparison(&solver_guesses);
+    }
+    ui::show_seed(game.seed());
+    outln!("═══════════════════════════════════════════");
+    true
+}
 
-        // Ask to play again
-        if !ui::play_again() {
-            println!("\n👋 Thanks for playing CipherMind!");
-            println!("Remember: Logic conquers all codes! 🧩\n");
-            break;
+/// Play one game where the computer cracks a code the player has in mind.
+/// Returns `false` if the player quit.
+fn play_codemaker(difficulty: Difficulty) -> bool {
+    if !solver::is_tractable(&difficulty) {
+        outln!(
+            "\n❌ Too many possible codes for me to search — codemaker mode supports at most {}.",
+            solver::MAX_SEARCH_SPACE
+        );
+        return true;
+    }
+    ui::print_codemaker_welcome(&difficulty);
+
+    let mut history: Vec<solver::Turn> = Vec::new();
+    while history.len() < difficulty.max_attempts {
+        let guess = match solver::knuth_next_guess(&difficulty, &history) {
+            Ok(Some(guess)) => guess,
+            Ok(None) => {
+                let conflict = solver::conflicting_turns(&difficulty, &history);
+                ui::show_conflict(&conflict.ok().flatten().unwrap_or_default());
+                return true;
+            }
+            Err(error) => {
+                outln!("\n❌ {}", error);
+                return true;
+            }
+        };
+
+        let Some(feedback) = ui::ask_feedback(history.len() + 1, &guess, difficulty.code_length)
+        else {
+            return false;
+        };
+        history.push((guess, feedback));
+
+        if feedback.exact_matches == difficulty.code_length {
+            outln!("\n═══════════════════════════════════════════");
+            outln!(
+                "🤖 Cracked it in {} {}!",
+                history.len(),
+                if history.len() == 1 { "guess" } else { "guesses" }
+            );
+            outln!("═══════════════════════════════════════════");
+            return true;
+        }
+    }
+
+    outln!("\n═══════════════════════════════════════════");
+    outln!("😵 I'm out of guesses — you win this round!");
+    outln!("═══════════════════════════════════════════");
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    /// Win `game` by following the Knuth solver.
+    fn win(game: &mut Game) {
+        let mut turns = Vec::new();
+        while !game.is_over() {
+            let guess = solver::knuth_next_guess(game.difficulty(), &turns).unwrap().unwrap();
+            let (feedback, _) = game.submit_guess(&guess).unwrap();
+            turns.push((guess, feedback));
         }
+        assert_eq!(game.outcome(), Some(Outcome::Won));
+    }
+
+    #[test]
+    fn test_only_fresh_secrets_are_ranked() {
+        let dir = env::temp_dir().join(format!("ciphermind-main-test-{}", process::id()));
+        let path = dir.join("leaderboard.txt");
+
+        let (mut seeded, origin) = new_game(Difficulty::EASY, Some(42));
+        assert_eq!(origin, Origin::Seeded);
+        win(&mut seeded);
+        for origin in [Origin::Seeded, Origin::Resumed, Origin::Daily] {
+            record_leaderboard(&seeded, "ada", origin, &path);
+        }
+        assert!(!path.exists());
+
+        let (mut fresh, origin) = new_game(Difficulty::EASY, None);
+        win(&mut fresh);
+        record_leaderboard(&fresh, "ada", origin, &path);
+        let board = Leaderboard::load(&path).unwrap();
+        assert_eq!(board.top(Difficulty::EASY.name).len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(args(&[])), Ok(Options::default()));
+        assert_eq!(parse_args(args(&["--seed", "42"])).unwrap().seed, Some(42));
+        assert_eq!(parse_args(args(&["--seed=7"])).unwrap().seed, Some(7));
+        assert_eq!(
+            parse_args(args(&["--resume", "game.txt", "--seed", "1"])),
+            Ok(Options {
+                seed: Some(1),
+                resume: Some(PathBuf::from("game.txt")),
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["--feedback=text"])).unwrap().feedback,
+            Some(FeedbackStyle::Text)
+        );
+        assert_eq!(
+            parse_args(args(&["--style", "shapes"])).unwrap().style,
+            Some(RenderStyle::Shapes)
+        );
+        assert_eq!(
+            parse_args(args(&["--no-color"])).unwrap().style,
+            Some(RenderStyle::Ascii)
+        );
+        assert!(parse_args(args(&["--style=plaid"])).is_err());
+        assert!(parse_args(args(&["--no-color=yes"])).is_err());
+        assert!(parse_args(args(&["--seed"])).is_err());
+        assert!(parse_args(args(&["--seed", "abc"])).is_err());
+        assert!(parse_args(args(&["--resume"])).is_err());
+        assert!(parse_args(args(&["--color"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_subcommands() {
+        assert_eq!(
+            parse_args(args(&["play", "--difficulty", "hard", "--seed", "9"])),
+            Ok(Options {
+                command: Command::Play,
+                difficulty: Some(Difficulty::HARD),
+                seed: Some(9),
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["--difficulty=Easy", "solve"])).unwrap().command,
+            Command::Solve
+        );
+        assert_eq!(
+            parse_args(args(&["replay", "games/1.txt"])).unwrap().command,
+            Command::Replay(PathBuf::from("games/1.txt"))
+        );
+        assert_eq!(parse_args(args(&["daily"])).unwrap().command, Command::Daily);
+        assert_eq!(parse_args(args(&["stats", "--no-color"])).unwrap().command, Command::Stats);
+        assert_eq!(parse_args(args(&["play", "-h"])).unwrap().command, Command::Help);
+        assert_eq!(parse_args(args(&["help"])).unwrap().command, Command::Help);
+
+        assert!(parse_args(args(&["replay"])).is_err());
+        assert!(parse_args(args(&["dance"])).is_err());
+        assert!(parse_args(args(&["play", "solve"])).is_err());
+        assert!(parse_args(args(&["play", "--difficulty", "impossible"])).is_err());
+        assert!(parse_args(args(&["--difficulty", "hard"])).is_err());
+        assert!(parse_args(args(&["solve", "--seed", "3"])).is_err());
+        assert!(parse_args(args(&["stats", "--resume", "save.txt"])).is_err());
     }
 }
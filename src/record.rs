@@ -0,0 +1,273 @@
+//! Game records for replaying finished games — no terminal I/O lives here.
+//!
+//! A record is a plain text file in the spirit of chess PGN: `[Tag "value"]`
+//! lines describing the game, a blank line, then one numbered line per guess
+//! with its feedback and the time since the game started:
+//!
+//! ```text
+//! [Record "ciphermind 1"]
+//! [Player "ana"]
+//! [Difficulty "Classic 4 6 10 allowed no-blanks"]
+//! [Seed "42"]
+//! [Secret "RGBY"]
+//! [Hints "0"]
+//! [Started "1760000000000"]
+//! [Result "won"]
+//!
+//! 1. RRGG 1/1 5320ms
+//! 2. RGBY 4/0 12875ms
+//! ```
+//!
+//! Unlike saves, records are meant to be read and shared, so nothing is masked.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::game::{Difficulty, Feedback, Game, Outcome, TurnRecord};
+use crate::save::{self, SaveError};
+use crate::storage;
+
+/// First line of every record; bump the version when the format changes.
+const HEADER: &str = "[Record \"ciphermind 1\"]";
+
+/// A record read back from disk. The game's history holds the guesses and
+/// feedback exactly as recorded; they are not re-scored here.
+pub struct Record {
+    /// Who played.
+    pub player: String,
+    /// How the game ended.
+    pub outcome: Outcome,
+    /// The game as recorded, with its secret and turns.
+    pub game: Game,
+}
+
+/// Where the record for a game started at `game.started` is written.
+pub fn default_path(game: &Game) -> PathBuf {
+    storage::data_dir()
+        .join("games")
+        .join(format!("{}.txt", save::unix_millis(game.started)))
+}
+
+/// Write the record for `game` to `path`, creating its directory if needed.
+pub fn write(game: &Game, player: &str, outcome: Outcome, path: &Path) -> Result<(), SaveError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, encode(game, player, outcome))?;
+    Ok(())
+}
+
+/// Read a record back from `path`.
+pub fn load(path: &Path) -> Result<Record, SaveError> {
+    decode(&fs::read_to_string(path)?)
+}
+
+/// The record file contents for `game`.
+pub fn encode(game: &Game, player: &str, outcome: Outcome) -> String {
+    let seed = game.seed.map_or("none".to_string(), |seed| seed.to_string());
+    let mut text = format!("{}\n", HEADER);
+    for (tag, value) in [
+        ("Player", player.to_string()),
+        ("Difficulty", save::format_difficulty(game.difficulty())),
+        ("Seed", seed),
+        ("Secret", game.secret_code.to_string()),
+        ("Hints", game.hints_used().to_string()),
+        ("Started", save::unix_millis(game.started).to_string()),
+        ("Result", outcome.name().to_string()),
+    ] {
+        text.push_str(&format!("[{} \"{}\"]\n", tag, value));
+    }
+
+    text.push('\n');
+    for (i, turn) in game.history().iter().enumerate() {
+        let offset = turn.timestamp.duration_since(game.started).unwrap_or_default();
+        text.push_str(&format!(
+            "{}. {} {}/{} {}ms\n",
+            i + 1,
+            turn.guess,
+            turn.feedback.exact_matches,
+            turn.feedback.color_matches,
+            offset.as_millis()
+        ));
+    }
+    text
+}
+
+/// Parse record contents, checking the guesses against the rules.
+pub fn decode(contents: &str) -> Result<Record, SaveError> {
+    let mut lines = contents.lines();
+    let header = lines.next().unwrap_or_default();
+    if header != HEADER {
+        return Err(SaveError::Version(header.to_string()));
+    }
+
+    let mut tags = Vec::new();
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        tags.push(parse_tag(line).ok_or_else(|| malformed(line))?);
+    }
+    let tag = |name: &str| {
+        tags.iter()
+            .find(|(tag, _)| *tag == name)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| SaveError::Malformed(format!("missing [{}] tag", name)))
+    };
+
+    let difficulty = save::parse_difficulty(tag("Difficulty")?)?;
+    let seed = match tag("Seed")? {
+        "none" => None,
+        value => Some(value.parse().map_err(|_| malformed(value))?),
+    };
+    let hints = tag("Hints")?;
+    let hints_used = hints.parse().map_err(|_| malformed(hints))?;
+    let started_tag = tag("Started")?;
+    let millis = started_tag.parse().map_err(|_| malformed(started_tag))?;
+    let started = UNIX_EPOCH + Duration::from_millis(millis);
+    let result = tag("Result")?;
+    let outcome = Outcome::ALL
+        .into_iter()
+        .find(|outcome| outcome.name() == result)
+        .ok_or_else(|| malformed(result))?;
+
+    let secret_code = difficulty.parse_guess(tag("Secret")?)?;
+
+    let mut history = Vec::new();
+    for (i, line) in lines.filter(|line| !line.is_empty()).enumerate() {
+        let turn = parse_turn(&difficulty, started, line)?;
+        if !line.starts_with(&format!("{}. ", i + 1)) {
+            return Err(malformed(line));
+        }
+        history.push(turn);
+    }
+    if history.len() > difficulty.max_attempts {
+        return Err(SaveError::Invalid("more guesses than the difficulty allows".to_string()));
+    }
+
+    let mut game = Game::restore(difficulty, secret_code, seed, hints_used, started, history);
+    if outcome == Outcome::Abandoned {
+        game.abandon();
+    }
+    if game.outcome() != Some(outcome) {
+        return Err(SaveError::Invalid(format!(
+            "the guesses don't end in a {} game",
+            outcome.name()
+        )));
+    }
+
+    Ok(Record {
+        player: tag("Player")?.to_string(),
+        outcome,
+        game,
+    })
+}
+
+/// Split a `[Tag "value"]` line.
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+    let inner = line.strip_prefix('[')?.strip_suffix("\"]")?;
+    inner.split_once(" \"")
+}
+
+/// One `N. GUESS EXACT/COLOR OFFSETms` line.
+fn parse_turn(
+    difficulty: &Difficulty,
+    started: SystemTime,
+    line: &str,
+) -> Result<TurnRecord, SaveError> {
+    let parts: Vec<&str> = line.split(' ').collect();
+    let [_, guess, feedback, offset] = parts[..] else {
+        return Err(malformed(line));
+    };
+
+    let guess = difficulty.parse_guess(guess)?;
+    let (exact, color) = feedback.split_once('/').ok_or_else(|| malformed(line))?;
+    let feedback = Feedback {
+        exact_matches: exact.parse().map_err(|_| malformed(line))?,
+        color_matches: color.parse().map_err(|_| malformed(line))?,
+    };
+    let millis: u64 = offset
+        .strip_suffix("ms")
+        .and_then(|millis| millis.parse().ok())
+        .ok_or_else(|| malformed(line))?;
+
+    Ok(TurnRecord {
+        guess,
+        feedback,
+        timestamp: started + Duration::from_millis(millis),
+    })
+}
+
+fn malformed(line: &str) -> SaveError {
+    SaveError::Malformed(line.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::code;
+    use crate::game::{game_with_secret, Difficulty, GuessError};
+
+    fn finished_game() -> Game {
+        let mut game = game_with_secret(Difficulty::HARD, "RGBYM");
+        game.seed = Some(7);
+        game.submit_guess(&code("RRGGB")).unwrap();
+        game.record_hint();
+        game.submit_guess(&code("RGBYM")).unwrap();
+        game
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let game = finished_game();
+        let contents = encode(&game, "Bo Li", Outcome::Won);
+        assert!(contents.contains("[Secret \"RGBYM\"]"));
+        assert!(contents.contains("\n2. RGBYM 5/0 "));
+
+        let record = decode(&contents).unwrap();
+        assert_eq!(record.player, "Bo Li");
+        assert_eq!(record.outcome, Outcome::Won);
+        assert_eq!(record.game.difficulty(), game.difficulty());
+        assert_eq!(record.game.secret_code, game.secret_code);
+        assert_eq!(record.game.seed(), Some(7));
+        assert_eq!(record.game.hints_used(), 1);
+        assert_eq!(record.game.history().len(), 2);
+        for (read, original) in record.game.history().iter().zip(game.history()) {
+            assert_eq!(read.guess, original.guess);
+            assert_eq!(read.feedback, original.feedback);
+        }
+    }
+
+    #[test]
+    fn test_feedback_is_kept_as_recorded() {
+        let contents = encode(&finished_game(), "ana", Outcome::Won).replace(" 1/2 ", " 3/0 ");
+        let record = decode(&contents).unwrap();
+        let turn = &record.game.history()[0];
+        assert_eq!(turn.feedback, Feedback { exact_matches: 3, color_matches: 0 });
+        assert_ne!(record.game.get_feedback(&turn.guess).unwrap(), turn.feedback);
+    }
+
+    #[test]
+    fn test_rejects_bad_records() {
+        let contents = encode(&finished_game(), "ana", Outcome::Won);
+        assert!(matches!(decode("hello"), Err(SaveError::Version(_))));
+        assert!(matches!(
+            decode(&contents.replace("[Result \"won\"]\n", "")),
+            Err(SaveError::Malformed(_))
+        ));
+        assert!(matches!(
+            decode(&contents.replace("1. RRGGB", "1. RRGGX")),
+            Err(SaveError::Guess(GuessError::IllegalSymbol {
+                symbol: 'X',
+                position: 4,
+                ..
+            }))
+        ));
+        assert!(decode(&contents.replace("2. RGBYM", "3. RGBYM")).is_err());
+        assert!(matches!(
+            decode(&contents.replace("\"won\"", "\"lost\"")),
+            Err(SaveError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,491 @@
+//! Saving and resuming games in progress — no terminal I/O lives here.
+//!
+//! A save is a small versioned text file. The secret (and the seed, which
+//! would reproduce it) are masked so opening the file doesn't give them
+//! away, and a checksum over every line rejects files that were edited by
+//! hand.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::game::{Difficulty, DifficultyError, Feedback, Game, GuessError, Repeats, TurnRecord};
+use crate::storage;
+
+/// First line of every save file; bump the version when the format changes.
+const HEADER: &str = "ciphermind-save 3";
+
+/// Mixed into the checksum and the secret mask so neither can be recomputed
+/// by just reading this format description.
+const SALT: &[u8] = b"ciphermind/save/v3";
+
+/// Why a save (or game record) file couldn't be written or read back.
+#[derive(Debug)]
+pub enum SaveError {
+    /// The file couldn't be read or written.
+    Io(io::Error),
+    /// The file isn't the expected kind, or comes from an unsupported version.
+    Version(String),
+    /// The file is a save in an older format, which can't be resumed.
+    Outdated(u32),
+    /// A line is missing or doesn't parse.
+    Malformed(String),
+    /// The checksum doesn't match — the file was edited or corrupted.
+    Tampered,
+    /// The saved difficulty can't be played.
+    Difficulty(DifficultyError),
+    /// The secret or a guess breaks the difficulty's rules.
+    Guess(GuessError),
+    /// The contents parse but break the game's rules.
+    Invalid(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "{}", error),
+            SaveError::Version(header) => write!(f, "not a CipherMind file (found '{}')", header),
+            SaveError::Outdated(version) => write!(
+                f,
+                "this save is from an older version of CipherMind (format {}) and can't be resumed",
+                version
+            ),
+            SaveError::Malformed(line) => write!(f, "unreadable line '{}'", line),
+            SaveError::Tampered => write!(f, "the save file has been modified"),
+            SaveError::Difficulty(error) => write!(f, "invalid difficulty: {}", error),
+            SaveError::Guess(error) => write!(f, "invalid code: {}", error),
+            SaveError::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(error) => Some(error),
+            SaveError::Difficulty(error) => Some(error),
+            SaveError::Guess(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+impl From<DifficultyError> for SaveError {
+    fn from(error: DifficultyError) -> Self {
+        SaveError::Difficulty(error)
+    }
+}
+
+impl From<GuessError> for SaveError {
+    fn from(error: GuessError) -> Self {
+        SaveError::Guess(error)
+    }
+}
+
+/// Where `save` writes by default.
+pub fn default_path() -> PathBuf {
+    storage::data_dir().join("save.txt")
+}
+
+/// Write `game` to `path`, creating its directory if needed.
+pub fn save(game: &Game, path: &Path) -> Result<(), SaveError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, encode(game))?;
+    Ok(())
+}
+
+/// Read a game back from `path`, checking it against the rules.
+pub fn load(path: &Path) -> Result<Game, SaveError> {
+    decode(&fs::read_to_string(path)?)
+}
+
+/// The save file contents for `game`.
+pub fn encode(game: &Game) -> String {
+    let started = unix_millis(game.started);
+    let mut lines = vec![
+        HEADER.to_string(),
+        format!("difficulty {}", format_difficulty(game.difficulty())),
+        match game.seed {
+            Some(seed) => format!("seed {}", mask(started, "seed", &seed.to_string())),
+            None => "seed none".to_string(),
+        },
+        format!("hints {}", game.hints_used()),
+        format!("started {}", started),
+        format!(
+            "secret {}",
+            mask(started, "secret", &game.secret_code.to_string())
+        ),
+    ];
+    for turn in game.history() {
+        lines.push(format!(
+            "turn {} {} {} {}",
+            turn.guess,
+            turn.feedback.exact_matches,
+            turn.feedback.color_matches,
+            unix_millis(turn.timestamp)
+        ));
+    }
+
+    let check = checksum(&lines);
+    lines.push(format!("check {:016x}", check));
+    lines.join("\n") + "\n"
+}
+
+/// Parse and validate save file contents.
+pub fn decode(contents: &str) -> Result<Game, SaveError> {
+    let mut lines: Vec<&str> = contents.lines().collect();
+
+    check_header(lines.first().copied().unwrap_or_default())?;
+
+    let check_line = lines.pop().unwrap_or_default();
+    let check = field(check_line, "check")
+        .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| malformed(check_line))?;
+    let owned: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    if checksum(&owned) != check {
+        return Err(SaveError::Tampered);
+    }
+
+    let mut rest = lines[1..].iter().copied();
+    let mut next = |key: &str| {
+        let line = rest.next().unwrap_or_default();
+        field(line, key).ok_or_else(|| malformed(line))
+    };
+
+    let difficulty = parse_difficulty(next("difficulty")?)?;
+    let seed_line = next("seed")?;
+    let hints_line = next("hints")?;
+    let hints_used = hints_line.parse().map_err(|_| malformed(hints_line))?;
+    let started_line = next("started")?;
+    let millis: u64 = started_line.parse().map_err(|_| malformed(started_line))?;
+    let started = UNIX_EPOCH + Duration::from_millis(millis);
+    let seed = match seed_line {
+        "none" => None,
+        value => Some(
+            unmask(millis.into(), "seed", value)
+                .and_then(|seed| seed.parse().ok())
+                .ok_or_else(|| malformed("seed"))?,
+        ),
+    };
+    let secret = unmask(millis.into(), "secret", next("secret")?)
+        .ok_or_else(|| malformed("secret"))?;
+
+    // Check guesses with the game's own validation, then rebuild the history.
+    let secret_code = difficulty.parse_guess(&secret)?;
+    if difficulty.repeats != Repeats::Allowed && secret_code.find_repeat().is_some() {
+        return Err(SaveError::Invalid("the secret repeats a color".to_string()));
+    }
+
+    let mut history = Vec::new();
+    for line in &lines[6..] {
+        history.push(parse_turn(&difficulty, line)?);
+    }
+
+    let game = Game::restore(difficulty, secret_code, seed, hints_used, started, history);
+    for turn in game.history() {
+        if game.get_feedback(&turn.guess) != Ok(turn.feedback) {
+            return Err(SaveError::Invalid(
+                "a recorded feedback doesn't match the secret".to_string(),
+            ));
+        }
+    }
+    if game.is_over() {
+        return Err(SaveError::Invalid("that game is already over".to_string()));
+    }
+
+    Ok(game)
+}
+
+/// `NAME LENGTH COLORS ATTEMPTS REPEATS BLANKS`, as read by [`parse_difficulty`].
+pub fn format_difficulty(d: &Difficulty) -> String {
+    format!(
+        "{} {} {} {} {} {}",
+        d.name,
+        d.code_length,
+        d.num_colors,
+        d.max_attempts,
+        repeats_name(d.repeats),
+        if d.blanks { "blanks" } else { "no-blanks" }
+    )
+}
+
+/// Milliseconds since the Unix epoch (0 for times before it).
+pub fn unix_millis(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis())
+}
+
+/// Accept only the current [`HEADER`], telling saves in an older format apart
+/// from files that aren't saves at all.
+fn check_header(header: &str) -> Result<(), SaveError> {
+    if header == HEADER {
+        return Ok(());
+    }
+    let version = |header: &str| header.strip_prefix("ciphermind-save ")?.parse::<u32>().ok();
+    Err(match (version(header), version(HEADER)) {
+        (Some(found), Some(current)) if found < current => SaveError::Outdated(found),
+        _ => SaveError::Version(header.to_string()),
+    })
+}
+
+/// The value after `key ` on a line, if the line starts with that key.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.strip_prefix(key)?.strip_prefix(' ')
+}
+
+fn malformed(line: &str) -> SaveError {
+    SaveError::Malformed(line.to_string())
+}
+
+fn repeats_name(repeats: Repeats) -> &'static str {
+    match repeats {
+        Repeats::Allowed => "allowed",
+        Repeats::UniqueSecret => "unique-secret",
+        Repeats::Forbidden => "forbidden",
+    }
+}
+
+/// Rebuild a difficulty, insisting that preset names still mean the preset.
+pub fn parse_difficulty(value: &str) -> Result<Difficulty, SaveError> {
+    let parts: Vec<&str> = value.split(' ').collect();
+    let [name, length, colors, attempts, repeats, blanks] = parts[..] else {
+        return Err(malformed(value));
+    };
+    let number = |part: &str| part.parse::<usize>().map_err(|_| malformed(value));
+    let custom = Difficulty::custom(number(length)?, number(colors)?, number(attempts)?)?;
+
+    let base = match Difficulty::ALL.iter().find(|preset| preset.name == name) {
+        Some(preset)
+            if (preset.code_length, preset.num_colors, preset.max_attempts)
+                == (custom.code_length, custom.num_colors, custom.max_attempts) =>
+        {
+            *preset
+        }
+        Some(_) => {
+            return Err(SaveError::Invalid(format!(
+                "the {} settings don't match the preset",
+                name
+            )))
+        }
+        None if name == custom.name => custom,
+        None => return Err(malformed(value)),
+    };
+
+    let repeats = Repeats::ALL
+        .into_iter()
+        .find(|&r| repeats_name(r) == repeats)
+        .ok_or_else(|| malformed(value))?;
+    let blanks = match blanks {
+        "blanks" => true,
+        "no-blanks" => false,
+        _ => return Err(malformed(value)),
+    };
+
+    // Blanks first: they can supply the symbols a unique-colors rule needs.
+    Ok(base.with_blanks(blanks)?.with_repeats(repeats)?)
+}
+
+/// One `turn GUESS EXACT COLOR MILLIS` line.
+fn parse_turn(difficulty: &Difficulty, line: &str) -> Result<TurnRecord, SaveError> {
+    let value = field(line, "turn").ok_or_else(|| malformed(line))?;
+    let parts: Vec<&str> = value.split(' ').collect();
+    let [guess, exact, color, millis] = parts[..] else {
+        return Err(malformed(line));
+    };
+
+    let guess = difficulty.parse_guess(guess)?;
+    let feedback = Feedback {
+        exact_matches: exact.parse().map_err(|_| malformed(line))?,
+        color_matches: color.parse().map_err(|_| malformed(line))?,
+    };
+    let millis: u64 = millis.parse().map_err(|_| malformed(line))?;
+
+    Ok(TurnRecord {
+        guess,
+        feedback,
+        timestamp: UNIX_EPOCH + Duration::from_millis(millis),
+    })
+}
+
+/// Salted hash over every line, so edits without the salt are caught.
+fn checksum(lines: &[String]) -> u64 {
+    let mut bytes = SALT.to_vec();
+    for line in lines {
+        bytes.extend_from_slice(line.as_bytes());
+        bytes.push(b'\n');
+    }
+    storage::fnv1a(&bytes)
+}
+
+/// Keystream byte `i` for masking the field called `label` in a save whose
+/// game started at `started` (Unix millis). The start time differs from file
+/// to file, so no two saves share a keystream.
+fn mask_byte(started: u128, label: &str, i: usize) -> u8 {
+    let mut bytes = SALT.to_vec();
+    bytes.extend_from_slice(&started.to_le_bytes());
+    bytes.extend_from_slice(label.as_bytes());
+    bytes.extend_from_slice(&i.to_le_bytes());
+    storage::fnv1a(&bytes) as u8
+}
+
+/// Hide `text` behind the keystream for `label`, hex-encoded.
+fn mask(started: u128, label: &str, text: &str) -> String {
+    text.bytes()
+        .enumerate()
+        .map(|(i, byte)| format!("{:02x}", byte ^ mask_byte(started, label, i)))
+        .collect()
+}
+
+/// Reverse [`mask`], or `None` if the hex is malformed.
+fn unmask(started: u128, label: &str, hex: &str) -> Option<String> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..hex.len() / 2)
+        .map(|i| {
+            let byte = u8::from_str_radix(hex.get(2 * i..2 * i + 2)?, 16).ok()?;
+            Some(byte ^ mask_byte(started, label, i))
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::code;
+    use crate::game::game_with_secret;
+
+    fn game_in_progress() -> Game {
+        let difficulty = Difficulty::CLASSIC.with_blanks(true).unwrap();
+        let mut game = game_with_secret(difficulty, "RG_Y");
+        game.seed = Some(99);
+        game.submit_guess(&code("RRGG")).unwrap();
+        game.record_hint();
+        game.submit_guess(&code("B_YM")).unwrap();
+        game
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let game = game_in_progress();
+        let loaded = decode(&encode(&game)).unwrap();
+
+        assert_eq!(loaded.secret_code, game.secret_code);
+        assert_eq!(loaded.difficulty(), game.difficulty());
+        assert_eq!(loaded.attempts(), 2);
+        assert_eq!(loaded.seed, Some(99));
+        assert_eq!(loaded.hints_used(), 1);
+        assert_eq!(unix_millis(loaded.started), unix_millis(game.started));
+        assert_eq!(loaded.history().len(), 2);
+        for (saved, original) in loaded.history().iter().zip(game.history()) {
+            assert_eq!(saved.guess, original.guess);
+            assert_eq!(saved.feedback, original.feedback);
+        }
+    }
+
+    #[test]
+    fn test_secret_is_not_readable() {
+        let contents = encode(&game_in_progress());
+        assert!(!contents.contains("RG_Y"));
+        assert!(contents.lines().all(|line| line != "seed 99"));
+    }
+
+    #[test]
+    fn test_saves_mask_the_same_secret_differently() {
+        let masked = |millis| {
+            let mut game = game_in_progress();
+            game.started = UNIX_EPOCH + Duration::from_millis(millis);
+            let contents = encode(&game);
+            assert_eq!(decode(&contents).unwrap().secret_code, game.secret_code);
+            contents
+                .lines()
+                .filter(|line| line.starts_with("seed ") || line.starts_with("secret "))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert_ne!(masked(1_000), masked(2_000));
+    }
+
+    #[test]
+    fn test_edits_are_rejected() {
+        let contents = encode(&game_in_progress());
+
+        let edited = contents.replace("hints 1", "hints 0");
+        assert!(matches!(decode(&edited), Err(SaveError::Tampered)));
+
+        let truncated: String = contents.lines().take(3).collect::<Vec<_>>().join("\n");
+        assert!(decode(&truncated).is_err());
+
+        assert!(matches!(decode("hello"), Err(SaveError::Version(_))));
+    }
+
+    #[test]
+    fn test_older_saves_are_recognised() {
+        let old = encode(&game_in_progress()).replacen(HEADER, "ciphermind-save 1", 1);
+        let Err(error) = decode(&old) else {
+            panic!("an old save was accepted");
+        };
+        assert!(matches!(error, SaveError::Outdated(1)));
+        assert!(error.to_string().contains("older version"));
+
+        let newer = encode(&game_in_progress()).replacen(HEADER, "ciphermind-save 99", 1);
+        assert!(matches!(decode(&newer), Err(SaveError::Version(_))));
+    }
+
+    #[test]
+    fn test_finished_games_cannot_be_saved_back() {
+        let mut game = game_in_progress();
+        game.submit_guess(&code("RG_Y")).unwrap();
+        assert!(matches!(decode(&encode(&game)), Err(SaveError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_blanks_can_make_up_unique_colors() {
+        let difficulty = Difficulty::custom(3, 2, 10)
+            .and_then(|d| d.with_blanks(true))
+            .and_then(|d| d.with_repeats(Repeats::Forbidden))
+            .unwrap();
+        assert_eq!(parse_difficulty(&format_difficulty(&difficulty)).unwrap(), difficulty);
+    }
+
+    #[test]
+    fn test_preset_settings_must_match() {
+        let game = game_in_progress();
+        let mut difficulty = *game.difficulty();
+        difficulty.max_attempts = 20;
+        let game = Game::restore(
+            difficulty,
+            game.secret_code.clone(),
+            game.seed,
+            game.hints_used(),
+            game.started,
+            game.history().to_vec(),
+        );
+        assert!(matches!(decode(&encode(&game)), Err(SaveError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_save_and_load_file() {
+        let path = std::env::temp_dir()
+            .join(format!("ciphermind-save-test-{}", std::process::id()))
+            .join("save.txt");
+        let game = game_in_progress();
+
+        save(&game, &path).unwrap();
+        assert_eq!(load(&path).unwrap().secret_code, game.secret_code);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert!(matches!(load(&path), Err(SaveError::Io(_))));
+    }
+}
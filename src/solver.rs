@@ -0,0 +1,677 @@
+//! Code-breaking strategies that reason over the whole code space — no
+//! terminal I/O lives here.
+
+use crate::code::{Code, Peg};
+use crate::game::{Difficulty, Feedback, GuessError, Repeats};
+use crate::table::FeedbackTable;
+use std::fmt;
+
+/// One turn of play as seen by a solver: the guess and the feedback it earned.
+pub type Turn = (Code, Feedback);
+
+/// Largest code space the solver will search; bigger custom difficulties are
+/// played without candidate tracking, hints or analysis.
+pub const MAX_SEARCH_SPACE: usize = 10_000;
+
+/// Why the solver can't take on a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverError {
+    /// The code space is bigger than [`MAX_SEARCH_SPACE`].
+    TooLarge,
+    /// A guess or secret doesn't fit the difficulty.
+    Guess(GuessError),
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::TooLarge => write!(
+                f,
+                "Too many possible codes to search (the solver handles at most {}).",
+                MAX_SEARCH_SPACE
+            ),
+            SolverError::Guess(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+impl From<GuessError> for SolverError {
+    fn from(error: GuessError) -> Self {
+        SolverError::Guess(error)
+    }
+}
+
+/// Whether the code space at this difficulty is small enough to search.
+pub fn is_tractable(difficulty: &Difficulty) -> bool {
+    difficulty
+        .guess_space()
+        .is_some_and(|size| size <= MAX_SEARCH_SPACE)
+}
+
+/// Every code that could be the secret at this difficulty, in palette order
+/// (`RRRR`, `RRRG`, …). Fails if the space is too large to search.
+pub fn all_codes(difficulty: &Difficulty) -> Result<Vec<Code>, SolverError> {
+    codes(difficulty, difficulty.repeats == Repeats::Allowed)
+}
+
+/// Every code the rules allow as a guess — a superset of [`all_codes`] when
+/// only the secret is barred from repeating colors. Fails if the space is too
+/// large to search.
+pub fn guess_pool(difficulty: &Difficulty) -> Result<Vec<Code>, SolverError> {
+    codes(difficulty, difficulty.repeats != Repeats::Forbidden)
+}
+
+/// Every code at this difficulty in palette order, optionally skipping codes
+/// that use a color twice.
+fn codes(difficulty: &Difficulty, allow_repeats: bool) -> Result<Vec<Code>, SolverError> {
+    if !is_tractable(difficulty) {
+        return Err(SolverError::TooLarge);
+    }
+    let palette = difficulty.palette();
+    let pegs = palette.pegs();
+    let mut codes: Vec<Vec<Peg>> = vec![Vec::new()];
+
+    for _ in 0..difficulty.code_length {
+        let mut longer = Vec::with_capacity(codes.len() * pegs.len());
+        for prefix in codes {
+            for &peg in &pegs {
+                if allow_repeats || !prefix.contains(&peg) {
+                    let mut code = prefix.clone();
+                    code.push(peg);
+                    longer.push(code);
+                }
+            }
+        }
+        codes = longer;
+    }
+
+    Ok(codes
+        .into_iter()
+        .map(|pegs| Code::from_parts(pegs, palette))
+        .collect())
+}
+
+/// Whether `code` could still be the secret given every turn played so far.
+pub fn is_consistent(code: &Code, history: &[Turn]) -> bool {
+    history
+        .iter()
+        .all(|(guess, feedback)| code.score(guess) == Ok(*feedback))
+}
+
+/// Check every guess in `history` against the rules of `difficulty`.
+fn check_history(difficulty: &Difficulty, history: &[Turn]) -> Result<(), GuessError> {
+    history
+        .iter()
+        .try_for_each(|(guess, _)| difficulty.check_guess(guess))
+}
+
+/// Every code at this difficulty still consistent with `history`. Fails if
+/// the space is too large to search or a guess doesn't fit the difficulty.
+pub fn consistent_codes(
+    difficulty: &Difficulty,
+    history: &[Turn],
+) -> Result<Vec<Code>, SolverError> {
+    check_history(difficulty, history)?;
+    let codes = all_codes(difficulty)?;
+    let scorer = Scorer::new(difficulty, &codes);
+    let mut keep = vec![true; codes.len()];
+    for (guess, feedback) in history {
+        scorer.for_each(guess, |i, actual| keep[i] &= actual == Some(*feedback));
+    }
+    Ok(codes
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(code, keep)| keep.then_some(code))
+        .collect())
+}
+
+/// When no code fits every turn in `history`, a minimal set of turns (indices
+/// into `history`) that already contradict each other: drop any one of them
+/// and some code fits the rest. It isn't always the smallest such set. Returns
+/// `None` if some code is consistent with the whole history, and fails if the
+/// space is too large to search or a guess doesn't fit the difficulty.
+pub fn conflicting_turns(
+    difficulty: &Difficulty,
+    history: &[Turn],
+) -> Result<Option<Vec<usize>>, SolverError> {
+    check_history(difficulty, history)?;
+    let codes = all_codes(difficulty)?;
+    let satisfiable = |turns: &[usize]| {
+        codes.iter().any(|code| {
+            turns.iter().all(|&i| {
+                let (guess, feedback) = &history[i];
+                code.score(guess) == Ok(*feedback)
+            })
+        })
+    };
+
+    // The first turn after which nothing fits is always part of the conflict.
+    let mut conflict: Vec<usize> = Vec::new();
+    for i in 0..history.len() {
+        conflict.push(i);
+        if !satisfiable(&conflict) {
+            break;
+        }
+    }
+    if satisfiable(&conflict) {
+        return Ok(None);
+    }
+
+    // Drop every earlier turn that isn't needed to keep the contradiction,
+    // latest first so the earliest culprits are the ones reported.
+    for i in (0..conflict.len() - 1).rev() {
+        let mut without = conflict.clone();
+        without.remove(i);
+        if !satisfiable(&without) {
+            conflict = without;
+        }
+    }
+
+    Ok(Some(conflict))
+}
+
+/// Drop every candidate that would not have produced `feedback` for `guess`.
+/// Fails, leaving `candidates` alone, if `guess` doesn't fit the difficulty.
+pub fn narrow(
+    difficulty: &Difficulty,
+    candidates: &mut Vec<Code>,
+    guess: &Code,
+    feedback: &Feedback,
+) -> Result<(), GuessError> {
+    difficulty.check_guess(guess)?;
+    candidates.retain(|code| code.score(guess) == Ok(*feedback));
+    Ok(())
+}
+
+/// Knuth's opening move: colors taken in pairs (`RRGG` on Classic, `RRGGB` on
+/// Hard), or the first distinct symbols (`RGBY`) when guesses can't repeat —
+/// the blank included, as [`Difficulty::validate`] counts it.
+fn opening(difficulty: &Difficulty) -> Code {
+    let pegs = difficulty.palette().pegs();
+    let pegs = if difficulty.repeats == Repeats::Forbidden {
+        pegs[..difficulty.code_length].to_vec()
+    } else {
+        let colors = &pegs[..difficulty.num_colors];
+        (0..difficulty.code_length)
+            .map(|i| colors[(i / 2).min(colors.len() - 1)])
+            .collect()
+    };
+    Code::from_parts(pegs, difficulty.palette())
+}
+
+/// Scores a fixed list of candidates against one guess after another, by
+/// [`FeedbackTable`] lookup when the code space fits in a shared table and
+/// with [`Code::score`] otherwise.
+struct Scorer<'a> {
+    candidates: &'a [Code],
+    /// The shared table and each candidate's index in it.
+    table: Option<(&'static FeedbackTable, Vec<usize>)>,
+}
+
+impl<'a> Scorer<'a> {
+    fn new(difficulty: &Difficulty, candidates: &'a [Code]) -> Self {
+        let table = FeedbackTable::shared(difficulty).and_then(|table| {
+            let indexes: Option<Vec<_>> = candidates.iter().map(|code| table.index(code)).collect();
+            indexes.map(|indexes| (table, indexes))
+        });
+        Scorer { candidates, table }
+    }
+
+    /// A scorer that never consults a table.
+    fn direct(candidates: &'a [Code]) -> Self {
+        Scorer { candidates, table: None }
+    }
+
+    /// Call `each` with every candidate's position and the feedback it gives
+    /// `guess`, in candidate order; `None` where the two can't be scored.
+    fn for_each(&self, guess: &Code, mut each: impl FnMut(usize, Option<Feedback>)) {
+        if let Some((table, indexes)) = &self.table {
+            if let Some(guess) = table.index(guess) {
+                for (i, &secret) in indexes.iter().enumerate() {
+                    each(i, Some(table.feedback(secret, guess)));
+                }
+                return;
+            }
+        }
+        for (i, candidate) in self.candidates.iter().enumerate() {
+            each(i, candidate.score(guess).ok());
+        }
+    }
+
+    /// How many candidates fall into each feedback class if `guess` is
+    /// played, indexed by `exact * (len + 1) + color` (cheaper than hashing
+    /// `Feedback`).
+    fn partition_counts(&self, guess: &Code) -> Vec<usize> {
+        let slots = guess.len() + 1;
+        let mut counts = vec![0; slots * slots];
+        self.for_each(guess, |_, feedback| {
+            if let Some(feedback) = feedback {
+                counts[feedback.exact_matches * slots + feedback.color_matches] += 1;
+            }
+        });
+        counts
+    }
+}
+
+/// Size of the largest class in `counts`.
+fn largest(counts: Vec<usize>) -> usize {
+    counts.into_iter().max().unwrap_or(0)
+}
+
+/// Shannon entropy, in bits, of splitting `total` candidates into `counts`.
+fn entropy(counts: Vec<usize>, total: usize) -> f64 {
+    let total = total as f64;
+    counts
+        .into_iter()
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Size of the largest feedback class if `guess` is played.
+pub fn worst_partition(guess: &Code, candidates: &[Code]) -> usize {
+    largest(Scorer::direct(candidates).partition_counts(guess))
+}
+
+/// Expected information (Shannon entropy, in bits) revealed by playing `guess`.
+pub fn expected_information(guess: &Code, candidates: &[Code]) -> f64 {
+    entropy(Scorer::direct(candidates).partition_counts(guess), candidates.len())
+}
+
+/// Pick the guess from `pool` whose worst-case feedback leaves the fewest
+/// candidates. Ties prefer guesses that could themselves be the secret, then
+/// the earliest code in `pool`.
+fn minimax_guess<'a>(pool: &'a [Code], scorer: &Scorer) -> Option<&'a Code> {
+    let candidates = scorer.candidates;
+    let mut best: Option<(&Code, usize, bool)> = None;
+
+    for guess in pool {
+        let worst = largest(scorer.partition_counts(guess));
+
+        let better = match best {
+            None => true,
+            Some((_, best_worst, _)) if worst < best_worst => true,
+            Some((_, best_worst, best_possible)) => {
+                worst == best_worst && !best_possible && candidates.contains(guess)
+            }
+        };
+        if better {
+            best = Some((guess, worst, candidates.contains(guess)));
+        }
+    }
+
+    best.map(|(guess, _, _)| guess)
+}
+
+/// The guess with the highest expected information gain over `candidates`,
+/// together with that gain in bits. Ties prefer guesses that could themselves
+/// be the secret. Returns `None` if there are no candidates, and fails if the
+/// space is too large to search.
+pub fn best_entropy_guess(
+    difficulty: &Difficulty,
+    candidates: &[Code],
+) -> Result<Option<(Code, f64)>, SolverError> {
+    let pool = guess_pool(difficulty)?;
+    match candidates {
+        [] => return Ok(None),
+        [only] => return Ok(Some((only.clone(), 0.0))),
+        _ => {}
+    }
+
+    let scorer = Scorer::new(difficulty, candidates);
+    let mut best: Option<(Code, f64, bool)> = None;
+    for guess in pool {
+        let bits = entropy(scorer.partition_counts(&guess), candidates.len());
+
+        let better = match &best {
+            None => true,
+            Some((_, best_bits, _)) if bits > best_bits + f64::EPSILON => true,
+            Some((_, best_bits, best_possible)) => {
+                (bits - best_bits).abs() <= f64::EPSILON
+                    && !best_possible
+                    && candidates.contains(&guess)
+            }
+        };
+        if better {
+            let possible = candidates.contains(&guess);
+            best = Some((guess, bits, possible));
+        }
+    }
+
+    Ok(best.map(|(guess, bits, _)| (guess, bits)))
+}
+
+/// The next guess under Knuth's five-guess minimax strategy, or `None` if no
+/// code is consistent with `history`. Fails if the space is too large to
+/// search or a guess doesn't fit the difficulty.
+pub fn knuth_next_guess(
+    difficulty: &Difficulty,
+    history: &[Turn],
+) -> Result<Option<Code>, SolverError> {
+    let candidates = consistent_codes(difficulty, history)?;
+
+    Ok(match candidates.len() {
+        0 => None,
+        1 => Some(candidates[0].clone()),
+        _ if history.is_empty() => Some(opening(difficulty)),
+        _ => {
+            let scorer = Scorer::new(difficulty, &candidates);
+            minimax_guess(&guess_pool(difficulty)?, &scorer).cloned()
+        }
+    })
+}
+
+/// Play the Knuth solver against `secret`, returning every guess it makes
+/// (the last one is the secret itself). Fails if the space is too large to
+/// search or the secret doesn't fit the difficulty.
+pub fn solve(difficulty: &Difficulty, secret: &Code) -> Result<Vec<Code>, SolverError> {
+    solve_from(difficulty, &[], secret)
+}
+
+/// Like [`solve`], but picking up from a position where `history` has already
+/// been played. Only the solver's own guesses are returned.
+pub fn solve_from(
+    difficulty: &Difficulty,
+    history: &[Turn],
+    secret: &Code,
+) -> Result<Vec<Code>, SolverError> {
+    difficulty.check_guess(secret)?;
+    let mut history = history.to_vec();
+    let played = history.len();
+
+    while let Some(guess) = knuth_next_guess(difficulty, &history)? {
+        let feedback = secret.score(&guess)?;
+        history.push((guess, feedback));
+        if feedback.exact_matches == difficulty.code_length {
+            break;
+        }
+    }
+
+    Ok(history.into_iter().skip(played).map(|(guess, _)| guess).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::code;
+    use std::collections::HashSet;
+
+    /// Walk the solver's whole decision tree from `history`, returning the
+    /// most guesses it needs for any secret still consistent with it.
+    fn worst_case_guesses(difficulty: &Difficulty, history: &mut Vec<Turn>) -> usize {
+        let guess = knuth_next_guess(difficulty, history).unwrap().expect("a consistent code");
+        let candidates = consistent_codes(difficulty, history).unwrap();
+
+        let feedbacks: HashSet<Feedback> = candidates.iter().map(|c| c.score(&guess).unwrap()).collect();
+
+        let mut worst = 0;
+        for feedback in feedbacks {
+            let depth = if feedback.exact_matches == difficulty.code_length {
+                1
+            } else {
+                history.push((guess.clone(), feedback));
+                let depth = 1 + worst_case_guesses(difficulty, history);
+                history.pop();
+                depth
+            };
+            worst = worst.max(depth);
+        }
+        worst
+    }
+
+    #[test]
+    fn test_all_codes_covers_space() {
+        let easy = all_codes(&Difficulty::EASY).unwrap();
+        assert_eq!(easy.len(), 4usize.pow(4));
+        assert_eq!(easy[0], code("RRRR"));
+        assert_eq!(easy[255], code("YYYY"));
+
+        assert_eq!(all_codes(&Difficulty::CLASSIC).unwrap().len(), 6usize.pow(4));
+        assert_eq!(all_codes(&Difficulty::HARD).unwrap().len(), 6usize.pow(5));
+    }
+
+    #[test]
+    fn test_presets_are_tractable() {
+        for difficulty in Difficulty::ALL {
+            assert!(is_tractable(&difficulty));
+        }
+        assert!(!is_tractable(&Difficulty::custom(8, 10, 12).unwrap()));
+    }
+
+    #[test]
+    fn test_large_spaces_are_refused() {
+        // 12^10 codes: enumerating them would exhaust memory.
+        let huge = Difficulty::custom(10, 12, 12).unwrap();
+        let secret = code("RGBYMCWOPK");
+        assert_eq!(all_codes(&huge), Err(SolverError::TooLarge));
+        assert_eq!(consistent_codes(&huge, &[]), Err(SolverError::TooLarge));
+        assert_eq!(knuth_next_guess(&huge, &[]), Err(SolverError::TooLarge));
+        let candidates = std::slice::from_ref(&secret);
+        assert_eq!(best_entropy_guess(&huge, candidates), Err(SolverError::TooLarge));
+        assert_eq!(solve(&huge, &secret), Err(SolverError::TooLarge));
+    }
+
+    #[test]
+    fn test_blanks_join_the_code_space() {
+        let difficulty = Difficulty::EASY.with_blanks(true).unwrap();
+        let codes = all_codes(&difficulty).unwrap();
+        assert_eq!(codes.len(), 5usize.pow(4));
+        assert!(codes.contains(&code("____")));
+
+        let secret = code("R_G_");
+        let guesses = solve(&difficulty, &secret).unwrap();
+        assert_eq!(guesses.last().unwrap(), &secret);
+    }
+
+    #[test]
+    fn test_is_consistent() {
+        let history = vec![(code("RRGG"), code("RGBY").score(&code("RRGG")).unwrap())];
+        assert!(is_consistent(&code("RGBY"), &history));
+        assert!(!is_consistent(&code("RRGG"), &history));
+    }
+
+    #[test]
+    fn test_narrow_matches_consistent_codes() {
+        let secret = code("RGBY");
+        let mut candidates = all_codes(&Difficulty::CLASSIC).unwrap();
+        let mut history = Vec::new();
+
+        for guess in [code("RRGG"), code("BYRM")] {
+            let feedback = secret.score(&guess).unwrap();
+            narrow(&Difficulty::CLASSIC, &mut candidates, &guess, &feedback).unwrap();
+            history.push((guess, feedback));
+
+            assert_eq!(candidates, consistent_codes(&Difficulty::CLASSIC, &history).unwrap());
+            assert!(candidates.contains(&secret));
+        }
+    }
+
+    #[test]
+    fn test_expected_information() {
+        // Four candidates split evenly by a guess reveal exactly two bits.
+        let candidates = vec![code("RRRR"), code("GRRR"), code("GGRR"), code("GGGR")];
+        let bits = expected_information(&code("GGGG"), &candidates);
+        assert!((bits - 2.0).abs() < 1e-9);
+
+        // A guess every candidate answers identically reveals nothing.
+        assert_eq!(expected_information(&code("BBBB"), &candidates), 0.0);
+    }
+
+    #[test]
+    fn test_best_entropy_guess() {
+        let secret = code("YBGR");
+        let guess = code("RRGG");
+        let mut candidates = all_codes(&Difficulty::EASY).unwrap();
+        narrow(&Difficulty::EASY, &mut candidates, &guess, &secret.score(&guess).unwrap()).unwrap();
+
+        let (best, bits) = best_entropy_guess(&Difficulty::EASY, &candidates).unwrap().unwrap();
+        assert!(bits > 0.0);
+        for code in all_codes(&Difficulty::EASY).unwrap() {
+            assert!(expected_information(&code, &candidates) <= bits + 1e-9);
+        }
+        assert_eq!(expected_information(&best, &candidates), bits);
+
+        assert_eq!(
+            best_entropy_guess(&Difficulty::EASY, std::slice::from_ref(&secret)).unwrap(),
+            Some((secret, 0.0))
+        );
+        assert_eq!(best_entropy_guess(&Difficulty::EASY, &[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_solve_from_resumes_position() {
+        let secret = code("BYRG");
+        let full = solve(&Difficulty::CLASSIC, &secret).unwrap();
+        let history = vec![(full[0].clone(), secret.score(&full[0]).unwrap())];
+        assert_eq!(solve_from(&Difficulty::CLASSIC, &history, &secret).unwrap(), full[1..]);
+    }
+
+    #[test]
+    fn test_opening_moves() {
+        assert_eq!(knuth_next_guess(&Difficulty::CLASSIC, &[]).unwrap(), Some(code("RRGG")));
+        assert_eq!(knuth_next_guess(&Difficulty::HARD, &[]).unwrap(), Some(code("RRGGB")));
+    }
+
+    #[test]
+    fn test_history_must_fit_the_difficulty() {
+        let short = code("RGB");
+        let feedback = Feedback {
+            exact_matches: 3,
+            color_matches: 0,
+        };
+        let history = vec![(short.clone(), feedback)];
+        let error = SolverError::Guess(GuessError::WrongLength {
+            expected: 4,
+            actual: 3,
+        });
+        assert_eq!(consistent_codes(&Difficulty::CLASSIC, &history), Err(error));
+        assert_eq!(conflicting_turns(&Difficulty::CLASSIC, &history), Err(error));
+        assert_eq!(knuth_next_guess(&Difficulty::CLASSIC, &history), Err(error));
+        assert_eq!(solve(&Difficulty::CLASSIC, &short), Err(error));
+
+        let mut candidates = all_codes(&Difficulty::CLASSIC).unwrap();
+        let narrowed = narrow(&Difficulty::CLASSIC, &mut candidates, &short, &feedback);
+        assert!(narrowed.is_err());
+        assert_eq!(candidates.len(), 1296);
+    }
+
+    #[test]
+    fn test_no_consistent_code() {
+        // Four exact then zero exact for the same guess can't both be true.
+        let guess = code("RGBY");
+        let history = vec![
+            (
+                guess.clone(),
+                Feedback {
+                    exact_matches: 4,
+                    color_matches: 0,
+                },
+            ),
+            (
+                guess,
+                Feedback {
+                    exact_matches: 0,
+                    color_matches: 0,
+                },
+            ),
+        ];
+        assert_eq!(knuth_next_guess(&Difficulty::CLASSIC, &history).unwrap(), None);
+    }
+
+    #[test]
+    fn test_conflicting_turns() {
+        let secret = code("RGBY");
+        let honest = |guess: Code| (guess.clone(), secret.score(&guess).unwrap());
+        let mut history = vec![honest(code("RRGG")), honest(code("BBYY")), honest(code("MMCC"))];
+        assert_eq!(conflicting_turns(&Difficulty::CLASSIC, &history).unwrap(), None);
+
+        // Claiming a perfect RRGG contradicts the earlier answer for RRGG.
+        history.push((
+            code("RRGG"),
+            Feedback {
+                exact_matches: 4,
+                color_matches: 0,
+            },
+        ));
+        assert_eq!(
+            conflicting_turns(&Difficulty::CLASSIC, &history).unwrap(),
+            Some(vec![0, 3])
+        );
+
+        // Feedback no code can produce conflicts on its own.
+        let impossible = vec![(
+            code("RGBY"),
+            Feedback {
+                exact_matches: 3,
+                color_matches: 1,
+            },
+        )];
+        assert_eq!(
+            conflicting_turns(&Difficulty::CLASSIC, &impossible).unwrap(),
+            Some(vec![0])
+        );
+    }
+
+    #[test]
+    fn test_unique_rules_restrict_codes() {
+        let unique = Difficulty::CLASSIC.with_repeats(Repeats::UniqueSecret).unwrap();
+        assert_eq!(all_codes(&unique).unwrap().len(), 360);
+        assert_eq!(guess_pool(&unique).unwrap().len(), 1296);
+        assert_eq!(all_codes(&unique).unwrap()[0], code("RGBY"));
+
+        let forbidden = Difficulty::CLASSIC.with_repeats(Repeats::Forbidden).unwrap();
+        assert_eq!(guess_pool(&forbidden).unwrap(), all_codes(&forbidden).unwrap());
+        assert_eq!(knuth_next_guess(&forbidden, &[]).unwrap(), Some(code("RGBY")));
+    }
+
+    #[test]
+    fn test_blanks_make_up_unique_openings() {
+        let difficulty = Difficulty::custom(3, 2, 10)
+            .and_then(|d| d.with_blanks(true))
+            .and_then(|d| d.with_repeats(Repeats::Forbidden))
+            .unwrap();
+        assert_eq!(knuth_next_guess(&difficulty, &[]).unwrap(), Some(code("RG_")));
+        let worst = worst_case_guesses(&difficulty, &mut Vec::new());
+        assert!(worst <= difficulty.max_attempts);
+    }
+
+    #[test]
+    fn test_forbidden_repeats_every_secret_within_budget() {
+        let forbidden = Difficulty::CLASSIC.with_repeats(Repeats::Forbidden).unwrap();
+        let worst = worst_case_guesses(&forbidden, &mut Vec::new());
+        assert!(worst <= forbidden.max_attempts);
+
+        let guesses = solve(&forbidden, &code("CYMG")).unwrap();
+        assert!(guesses.iter().all(|guess| guess_pool(&forbidden).unwrap().contains(guess)));
+    }
+
+    #[test]
+    fn test_classic_every_secret_within_five() {
+        assert!(worst_case_guesses(&Difficulty::CLASSIC, &mut Vec::new()) <= 5);
+    }
+
+    #[test]
+    fn test_easy_every_secret_within_budget() {
+        let worst = worst_case_guesses(&Difficulty::EASY, &mut Vec::new());
+        assert!(worst <= Difficulty::EASY.max_attempts);
+    }
+
+    #[test]
+    #[ignore = "walks all 7776 Hard secrets; run with --ignored --release"]
+    fn test_hard_every_secret_within_six() {
+        assert_eq!(worst_case_guesses(&Difficulty::HARD, &mut Vec::new()), 6);
+    }
+
+    #[test]
+    fn test_hard_solves_within_budget() {
+        let secret = code("MRCRY");
+        let guesses = solve(&Difficulty::HARD, &secret).unwrap();
+        assert_eq!(guesses.last().unwrap(), &secret);
+        assert!(guesses.len() <= Difficulty::HARD.max_attempts);
+    }
+}
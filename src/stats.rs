@@ -0,0 +1,225 @@
+//! Persistent player statistics per difficulty — no terminal I/O lives here.
+//!
+//! Records are kept per full rule set, so a custom size or a rule variant of
+//! a preset never shares a streak with the preset itself.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::game::Difficulty;
+use crate::storage;
+
+/// First line of the stats file; bump the version when the format changes.
+const HEADER: &str = "ciphermind-stats 1";
+
+/// Labels for the win-distribution buckets, matching the win messages.
+pub const BUCKET_LABELS: [&str; 4] = ["1", "2-3", "4-6", "7+"];
+
+/// Which distribution bucket a win in `attempts` guesses falls into.
+pub fn bucket(attempts: usize) -> usize {
+    match attempts {
+        0..=1 => 0,
+        2..=3 => 1,
+        4..=6 => 2,
+        _ => 3,
+    }
+}
+
+/// The record for one difficulty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DifficultyStats {
+    /// Games finished, won or lost; quitting after a guess counts as a loss.
+    pub played: usize,
+    /// Games won.
+    pub won: usize,
+    /// Wins since the last loss.
+    pub current_streak: usize,
+    /// Longest run of wins ever.
+    pub best_streak: usize,
+    /// Wins per bucket of [`BUCKET_LABELS`].
+    pub distribution: [usize; 4],
+}
+
+impl DifficultyStats {
+    /// Share of games won, from 0.0 to 1.0 (0.0 before any games).
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            self.won as f64 / self.played as f64
+        }
+    }
+}
+
+/// Every difficulty's record, keyed by the difficulty's `Display` string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Each difficulty's record.
+    pub by_difficulty: BTreeMap<String, DifficultyStats>,
+}
+
+impl Stats {
+    /// Read stats from `path`; a missing file means no games yet.
+    pub fn load(path: &Path) -> io::Result<Stats> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Stats::parse(&contents),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Stats::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Write stats to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    /// Count a finished game: `Some(attempts)` for a win, `None` for a loss.
+    pub fn record(&mut self, difficulty: &Difficulty, won_in: Option<usize>) {
+        let entry = self.by_difficulty.entry(difficulty.to_string()).or_default();
+        entry.played += 1;
+        match won_in {
+            Some(attempts) => {
+                entry.won += 1;
+                entry.current_streak += 1;
+                entry.best_streak = entry.best_streak.max(entry.current_streak);
+                entry.distribution[bucket(attempts)] += 1;
+            }
+            None => entry.current_streak = 0,
+        }
+    }
+
+    /// One tab-separated line per difficulty after the header.
+    fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for (name, s) in &self.by_difficulty {
+            let numbers = [s.played, s.won, s.current_streak, s.best_streak]
+                .iter()
+                .chain(&s.distribution)
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>();
+            text.push_str(&format!("{}\t{}\n", name, numbers.join("\t")));
+        }
+        text
+    }
+
+    fn parse(contents: &str) -> io::Result<Stats> {
+        let invalid = |what: &str| io::Error::new(io::ErrorKind::InvalidData, what.to_string());
+
+        let mut lines = contents.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("not a CipherMind stats file"));
+        }
+
+        let mut stats = Stats::default();
+        for line in lines.filter(|line| !line.is_empty()) {
+            let mut fields = line.split('\t');
+            let name = fields.next().unwrap_or_default();
+            let numbers = fields
+                .map(|field| field.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid(line))?;
+            let [played, won, current_streak, best_streak, b1, b2, b3, b4] = numbers[..] else {
+                return Err(invalid(line));
+            };
+            stats.by_difficulty.insert(
+                name.to_string(),
+                DifficultyStats {
+                    played,
+                    won,
+                    current_streak,
+                    best_streak,
+                    distribution: [b1, b2, b3, b4],
+                },
+            );
+        }
+        Ok(stats)
+    }
+}
+
+/// Where stats are kept.
+pub fn default_path() -> PathBuf {
+    storage::data_dir().join("stats.txt")
+}
+
+/// Forget every recorded game.
+pub fn reset(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets_match_win_messages() {
+        assert_eq!(bucket(1), 0);
+        assert_eq!(bucket(3), 1);
+        assert_eq!(bucket(4), 2);
+        assert_eq!(bucket(6), 2);
+        assert_eq!(bucket(7), 3);
+    }
+
+    #[test]
+    fn test_record_tracks_streaks_and_distribution() {
+        let mut stats = Stats::default();
+        stats.record(&Difficulty::CLASSIC, Some(5));
+        stats.record(&Difficulty::CLASSIC, Some(2));
+        stats.record(&Difficulty::CLASSIC, None);
+        stats.record(&Difficulty::CLASSIC, Some(9));
+        stats.record(&Difficulty::HARD, None);
+
+        let classic = &stats.by_difficulty[&Difficulty::CLASSIC.to_string()];
+        assert_eq!(classic.played, 4);
+        assert_eq!(classic.won, 3);
+        assert_eq!(classic.current_streak, 1);
+        assert_eq!(classic.best_streak, 2);
+        assert_eq!(classic.distribution, [0, 1, 1, 1]);
+        assert_eq!(classic.win_rate(), 0.75);
+        assert_eq!(stats.by_difficulty[&Difficulty::HARD.to_string()].win_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_rule_variants_are_kept_apart() {
+        let mut stats = Stats::default();
+        stats.record(&Difficulty::CLASSIC, Some(4));
+        stats.record(&Difficulty::CLASSIC.with_blanks(true).unwrap(), None);
+        stats.record(&Difficulty::custom(5, 8, 12).unwrap(), None);
+        stats.record(&Difficulty::custom(6, 8, 12).unwrap(), Some(7));
+
+        assert_eq!(stats.by_difficulty.len(), 4);
+        assert_eq!(stats.by_difficulty[&Difficulty::CLASSIC.to_string()].current_streak, 1);
+    }
+
+    #[test]
+    fn test_save_load_and_reset() {
+        let path = std::env::temp_dir()
+            .join(format!("ciphermind-stats-test-{}", std::process::id()))
+            .join("stats.txt");
+        assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+
+        let mut stats = Stats::default();
+        stats.record(&Difficulty::EASY, Some(1));
+        stats.record(&Difficulty::custom(5, 8, 12).unwrap(), None);
+        stats.save(&path).unwrap();
+        assert_eq!(Stats::load(&path).unwrap(), stats);
+
+        reset(&path).unwrap();
+        assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+        reset(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_garbage() {
+        assert!(Stats::parse("hello").is_err());
+        assert!(Stats::parse(&format!("{}\nClassic\t1\t2", HEADER)).is_err());
+    }
+}
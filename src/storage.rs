@@ -0,0 +1,27 @@
+//! Where CipherMind keeps files between runs — no terminal I/O lives here.
+
+use std::env;
+use std::path::PathBuf;
+
+/// Directory for local game data: `$CIPHERMIND_HOME` if set, otherwise
+/// `.ciphermind` in the user's home directory (or the working directory if
+/// no home can be found).
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("CIPHERMIND_HOME") {
+        return PathBuf::from(dir);
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".ciphermind")
+}
+
+/// 64-bit FNV-1a hash. Unlike `std`'s hasher it is stable across builds and
+/// platforms, so it is safe to write to disk or derive shared seeds from.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -0,0 +1,189 @@
+//! A precomputed feedback table for small code spaces — no terminal I/O
+//! lives here.
+//!
+//! Solvers and simulations score the same pairs of codes over and over. For
+//! spaces up to [`MAX_TABLE_SPACE`] codes (Classic has 1296), every pair can
+//! be scored once up front and looked up by [`Code::index`] afterwards. The
+//! solver does this through [`FeedbackTable::shared`].
+
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use crate::code::Code;
+use crate::game::{Difficulty, Feedback};
+
+/// Largest code space a table is built for. The table holds one byte per
+/// pair, so this caps it at about 6 MB.
+pub const MAX_TABLE_SPACE: usize = 2_500;
+
+/// Feedback for every (secret, guess) pair at one difficulty, filled in the
+/// first time it is used.
+pub struct FeedbackTable {
+    difficulty: Difficulty,
+    space: usize,
+    entries: OnceLock<Vec<u8>>,
+}
+
+impl FeedbackTable {
+    /// An empty table for `difficulty`, or `None` if its code space is larger
+    /// than [`MAX_TABLE_SPACE`]. Nothing is scored until the first lookup.
+    pub fn new(difficulty: &Difficulty) -> Option<FeedbackTable> {
+        let space = difficulty
+            .num_symbols()
+            .checked_pow(difficulty.code_length as u32)
+            .filter(|&space| space <= MAX_TABLE_SPACE)?;
+        Some(FeedbackTable {
+            difficulty: *difficulty,
+            space,
+            entries: OnceLock::new(),
+        })
+    }
+
+    /// The table for `difficulty`'s code space shared by the whole process,
+    /// or `None` if the space is too large. Difficulties with the same code
+    /// length and palette share one table, which lives until the process
+    /// exits; it is still only filled in on first lookup.
+    pub fn shared(difficulty: &Difficulty) -> Option<&'static FeedbackTable> {
+        static TABLES: Mutex<Vec<&'static FeedbackTable>> = Mutex::new(Vec::new());
+
+        let mut tables = TABLES.lock().unwrap_or_else(PoisonError::into_inner);
+        let found = tables.iter().find(|table| {
+            table.difficulty.code_length == difficulty.code_length
+                && table.difficulty.palette() == difficulty.palette()
+        });
+        if let Some(&table) = found {
+            return Some(table);
+        }
+        let table: &'static FeedbackTable = Box::leak(Box::new(FeedbackTable::new(difficulty)?));
+        tables.push(table);
+        Some(table)
+    }
+
+    /// How many codes the table covers: every code, repeats or not.
+    pub fn space(&self) -> usize {
+        self.space
+    }
+
+    /// Whether every pair has been scored yet.
+    pub fn is_built(&self) -> bool {
+        self.entries.get().is_some()
+    }
+
+    /// The feedback for the guess at index `guess` against the secret at
+    /// index `secret` (see [`Code::index`]). Builds the table on first use.
+    ///
+    /// # Panics
+    ///
+    /// If either index is outside the table; [`FeedbackTable::score`] looks
+    /// codes up without that risk.
+    pub fn feedback(&self, secret: usize, guess: usize) -> Feedback {
+        assert!(
+            secret < self.space && guess < self.space,
+            "index out of range for a table of {} codes",
+            self.space
+        );
+        let slots = self.difficulty.code_length + 1;
+        let entry = usize::from(self.entries()[secret * self.space + guess]);
+        Feedback {
+            exact_matches: entry / slots,
+            color_matches: entry % slots,
+        }
+    }
+
+    /// Where `code` sits in the table, or `None` if it doesn't fit the
+    /// table's code length and palette.
+    pub fn index(&self, code: &Code) -> Option<usize> {
+        if code.len() != self.difficulty.code_length {
+            return None;
+        }
+        self.difficulty.palette().code_index(code)
+    }
+
+    /// Score `guess` against `secret` by table lookup, or `None` if either
+    /// doesn't fit the table (see [`FeedbackTable::index`]).
+    pub fn score(&self, secret: &Code, guess: &Code) -> Option<Feedback> {
+        Some(self.feedback(self.index(secret)?, self.index(guess)?))
+    }
+
+    fn entries(&self) -> &[u8] {
+        self.entries.get_or_init(|| {
+            let slots = self.difficulty.code_length + 1;
+            let codes: Vec<Code> = (0..self.space)
+                .filter_map(|index| Code::from_index(index, &self.difficulty))
+                .collect();
+            let mut entries = Vec::with_capacity(self.space * self.space);
+            for secret in &codes {
+                for guess in &codes {
+                    let feedback = secret.score(guess).expect("every code is as long");
+                    entries.push((feedback.exact_matches * slots + feedback.color_matches) as u8);
+                }
+            }
+            entries
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::code;
+    use crate::game::Repeats;
+
+    #[test]
+    fn test_table_matches_scoring() {
+        let table = FeedbackTable::new(&Difficulty::CLASSIC).unwrap();
+        assert_eq!(table.space(), 1296);
+        assert!(!table.is_built());
+
+        let secret = code("RGBY");
+        for guess in [code("RRGG"), code("YBGR"), code("RGBY"), code("MCMC")] {
+            assert_eq!(table.score(&secret, &guess), Some(secret.score(&guess).unwrap()));
+        }
+        assert!(table.is_built());
+
+        let easy = FeedbackTable::new(&Difficulty::EASY).unwrap();
+        let codes = crate::solver::all_codes(&Difficulty::EASY).unwrap();
+        for (i, secret) in codes.iter().enumerate() {
+            for (j, guess) in codes.iter().enumerate() {
+                assert_eq!(easy.feedback(i, j), secret.score(guess).unwrap());
+            }
+        }
+
+        let blanks = FeedbackTable::new(&Difficulty::EASY.with_blanks(true).unwrap()).unwrap();
+        let feedback = blanks.score(&code("R__G"), &code("__RG"));
+        assert_eq!(feedback, Some(Feedback { exact_matches: 2, color_matches: 2 }));
+    }
+
+    #[test]
+    fn test_codes_from_elsewhere_are_refused() {
+        let easy = FeedbackTable::new(&Difficulty::EASY).unwrap();
+        assert_eq!(easy.index(&code("RGBM")), None);
+        assert_eq!(easy.index(&code("R_GB")), None);
+        assert_eq!(easy.index(&code("RGB")), None);
+        assert_eq!(easy.score(&code("RGBY"), &code("RGBM")), None);
+        assert_eq!(easy.score(&code("RGBYR"), &code("RGBYR")), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of range for a table of 256 codes")]
+    fn test_feedback_checks_both_indexes() {
+        // 1 * 256 + 256 is still inside the entries, but not a real pair.
+        FeedbackTable::new(&Difficulty::EASY).unwrap().feedback(1, 256);
+    }
+
+    #[test]
+    fn test_shared_tables_are_reused() {
+        let classic = FeedbackTable::shared(&Difficulty::CLASSIC).unwrap();
+        let unique = Difficulty::CLASSIC.with_repeats(Repeats::UniqueSecret).unwrap();
+        assert!(std::ptr::eq(classic, FeedbackTable::shared(&unique).unwrap()));
+
+        let blanks = Difficulty::CLASSIC.with_blanks(true).unwrap();
+        assert!(!std::ptr::eq(classic, FeedbackTable::shared(&blanks).unwrap()));
+        assert!(FeedbackTable::shared(&Difficulty::HARD).is_none());
+    }
+
+    #[test]
+    fn test_large_spaces_have_no_table() {
+        assert!(FeedbackTable::new(&Difficulty::HARD).is_none());
+        assert!(FeedbackTable::new(&Difficulty::custom(8, 10, 12).unwrap()).is_none());
+    }
+}
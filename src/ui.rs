@@ -4,85 +4,521 @@ use crossterm::{
     execute,
     style::{Color, Print, ResetColor, SetForegroundColor},
 };
-use std::io::{self, Write};
+use std::borrow::Cow;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
-use crate::game::{Difficulty, Feedback};
+use ciphermind::analysis::GuessReview;
+use ciphermind::code::Code;
+use ciphermind::game::{self, Difficulty, Feedback, Game, GuessError, Mode, Outcome, Repeats};
+use ciphermind::leaderboard::{self, Leaderboard};
+use ciphermind::record::Record;
+use ciphermind::stats::{self, Stats};
 
-/// Print a colored symbol based on the color character
-pub fn print_colored_symbol(color_char: char) {
-    let color = match color_char {
-        'R' => Color::Red,
-        'G' => Color::Green,
-        'B' => Color::Blue,
-        'Y' => Color::Yellow,
-        'M' => Color::Magenta,
-        'C' => Color::Cyan,
-        _ => Color::White,
+use crate::board::{self, Board};
+
+/// How pegs are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    /// The same `●` for every peg, told apart by hue alone.
+    Color,
+    /// Each peg's letter, in its hue.
+    Letters,
+    /// A different shape for each color, in its hue.
+    Shapes,
+    /// Plain letters with no escape codes, for logs and `NO_COLOR`.
+    Ascii,
+}
+
+impl RenderStyle {
+    pub const ALL: [RenderStyle; 4] = [
+        RenderStyle::Color,
+        RenderStyle::Letters,
+        RenderStyle::Shapes,
+        RenderStyle::Ascii,
+    ];
+
+    /// The name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            RenderStyle::Color => "color",
+            RenderStyle::Letters => "letters",
+            RenderStyle::Shapes => "shapes",
+            RenderStyle::Ascii => "ascii",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<RenderStyle> {
+        RenderStyle::ALL
+            .into_iter()
+            .find(|style| style.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The style to use when none was chosen: [`RenderStyle::Ascii`] if the
+    /// `NO_COLOR` environment variable is set or stdout isn't a terminal.
+    pub fn detect() -> RenderStyle {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        if no_color || !io::stdout().is_terminal() {
+            RenderStyle::Ascii
+        } else {
+            RenderStyle::Color
+        }
+    }
+
+    /// The terminal color (if any) and glyph for a peg.
+    pub fn peg(self, color_char: char) -> (Option<Color>, char) {
+        let (color, shape) = match color_char {
+            game::BLANK => (Color::DarkGrey, '○'),
+            'R' => (Color::Red, '●'),
+            'G' => (Color::Green, '▲'),
+            'B' => (Color::Blue, '■'),
+            'Y' => (Color::Yellow, '◆'),
+            'M' => (Color::Magenta, '★'),
+            'C' => (Color::Cyan, '✚'),
+            'W' => (Color::White, '♥'),
+            'O' => (Color::Rgb { r: 255, g: 140, b: 0 }, '♠'),
+            'P' => (Color::Rgb { r: 150, g: 80, b: 220 }, '♣'),
+            'K' => (Color::DarkGrey, '▼'),
+            'N' => (Color::Rgb { r: 140, g: 80, b: 30 }, '◐'),
+            'L' => (Color::Rgb { r: 170, g: 230, b: 50 }, '✖'),
+            _ => (Color::White, '?'),
+        };
+        match self {
+            RenderStyle::Color if color_char == game::BLANK => (Some(color), '○'),
+            RenderStyle::Color => (Some(color), '●'),
+            RenderStyle::Letters => (Some(color), color_char),
+            RenderStyle::Shapes => (Some(color), shape),
+            RenderStyle::Ascii => (None, color_char),
+        }
+    }
+
+    /// Glyphs for an exact key peg, a color key peg and an empty slot.
+    pub fn key_glyphs(self) -> [char; 3] {
+        match self {
+            RenderStyle::Ascii => ['X', 'o', '.'],
+            _ => ['●', '○', '·'],
+        }
+    }
+
+    /// The marker for the board row being played.
+    pub fn cursor(self) -> char {
+        match self {
+            RenderStyle::Ascii => '>',
+            _ => '▶',
+        }
+    }
+
+    /// `text` as this style prints it. [`RenderStyle::Ascii`] swaps frame,
+    /// arrow and share-grid glyphs for ASCII look-alikes and drops emoji along
+    /// with the space after them; every other style prints `text` as is.
+    pub fn text(self, text: &str) -> Cow<'_, str> {
+        if self != RenderStyle::Ascii || text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+        let mut plain = String::with_capacity(text.len());
+        let mut dropped = false;
+        // A newline is chained on so an emoji ending the text is handled like
+        // one ending a line; it is popped again at the end.
+        for ch in text.chars().chain(['\n']) {
+            if dropped && ch == '\n' && plain.ends_with(' ') {
+                plain.pop();
+            }
+            match ascii_glyph(ch) {
+                Some(glyph) => plain.push_str(glyph),
+                // Skip all the padding after a dropped glyph, so the text
+                // keeps the indent it would have had without one.
+                None if ch == ' ' && dropped => continue,
+                None if ch.is_ascii() => plain.push(ch),
+                None => {
+                    dropped = true;
+                    continue;
+                }
+            }
+            dropped = false;
+        }
+        plain.pop();
+        Cow::Owned(plain)
+    }
+}
+
+/// The ASCII stand-in for a frame, arrow or share-grid glyph.
+fn ascii_glyph(ch: char) -> Option<&'static str> {
+    let glyph = match ch {
+        '═' => "=",
+        '║' | '│' => "|",
+        '╔' | '╗' | '╚' | '╝' => "+",
+        '—' | '–' | '·' => "-",
+        '•' => "*",
+        '→' => "->",
+        '←' => "<-",
+        '↑' => "^",
+        '↓' => "v",
+        '▶' => ">",
+        '█' => "#",
+        '🟩' => "X",
+        '🟨' => "o",
+        '⬛' => ".",
+        _ => return None,
     };
+    Some(glyph)
+}
 
-    execute!(
-        io::stdout(),
-        SetForegroundColor(color),
-        Print(format!("●")),
-        ResetColor
-    )
-    .unwrap();
+/// The chosen [`RenderStyle`], as an index into [`RenderStyle::ALL`].
+static STYLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Choose how pegs are drawn from now on.
+pub fn set_style(style: RenderStyle) {
+    let index = RenderStyle::ALL.iter().position(|&s| s == style).unwrap_or(0);
+    STYLE.store(index, Ordering::Relaxed);
 }
 
-/// Render a guess and its feedback line
-pub fn show_guess_result(attempts: usize, guess: &[char], feedback: &Feedback) {
-    print!("  Guess {}: ", attempts);
-    for &color in guess {
-        print_colored_symbol(color);
-        print!(" ");
+/// How pegs are currently drawn.
+pub fn style() -> RenderStyle {
+    RenderStyle::ALL[STYLE.load(Ordering::Relaxed)]
+}
+
+/// `print!` through the current [`RenderStyle::text`], so ASCII output stays
+/// ASCII.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        print!("{}", $crate::ui::style().text(&format!($($arg)*)))
+    };
+}
+
+/// `println!` through the current [`RenderStyle::text`].
+macro_rules! outln {
+    () => {
+        println!()
+    };
+    ($($arg:tt)*) => {
+        println!("{}", $crate::ui::style().text(&format!($($arg)*)))
+    };
+}
+
+/// `eprintln!` through the current [`RenderStyle::text`].
+macro_rules! errln {
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::ui::style().text(&format!($($arg)*)))
+    };
+}
+
+pub(crate) use {errln, outln};
+
+/// How a guess's feedback is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackStyle {
+    /// Only the "1 exact, 2 colors" line, for screen readers.
+    Text,
+    /// Only the grid of key pegs.
+    Pegs,
+    /// The key-peg grid alongside the text line.
+    Both,
+}
+
+impl FeedbackStyle {
+    pub const ALL: [FeedbackStyle; 3] =
+        [FeedbackStyle::Both, FeedbackStyle::Text, FeedbackStyle::Pegs];
+
+    /// The name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            FeedbackStyle::Text => "text",
+            FeedbackStyle::Pegs => "pegs",
+            FeedbackStyle::Both => "both",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<FeedbackStyle> {
+        FeedbackStyle::ALL
+            .into_iter()
+            .find(|style| style.name().eq_ignore_ascii_case(name))
     }
 
-    println!(
-        "\n  → {} exact, {} color{}",
+    pub fn shows_text(self) -> bool {
+        self != FeedbackStyle::Pegs
+    }
+
+    pub fn shows_pegs(self) -> bool {
+        self != FeedbackStyle::Text
+    }
+}
+
+/// The chosen [`FeedbackStyle`], as an index into [`FeedbackStyle::ALL`]
+/// (so [`FeedbackStyle::Both`] until one is chosen).
+static FEEDBACK_STYLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Choose how feedback is shown from now on.
+pub fn set_feedback_style(style: FeedbackStyle) {
+    let index = FeedbackStyle::ALL.iter().position(|&s| s == style).unwrap_or(0);
+    FEEDBACK_STYLE.store(index, Ordering::Relaxed);
+}
+
+/// How feedback is currently shown.
+pub fn feedback_style() -> FeedbackStyle {
+    FeedbackStyle::ALL[FEEDBACK_STYLE.load(Ordering::Relaxed)]
+}
+
+/// "1 exact, 2 colors"
+pub fn feedback_text(feedback: &Feedback) -> String {
+    format!(
+        "{} exact, {} color{}",
         feedback.exact_matches,
         feedback.color_matches,
         if feedback.color_matches != 1 { "s" } else { "" }
-    );
+    )
+}
+
+/// Key pegs in two rows, like the small grid beside a row on a real board.
+/// Exact pegs come first, then color pegs, then empty holes, so the layout
+/// says nothing about which slots matched. `glyphs` are the exact, color and
+/// empty glyphs of a [`RenderStyle`]; both rows are padded to the same width.
+pub fn key_peg_grid(feedback: &Feedback, code_length: usize, glyphs: [char; 3]) -> [String; 2] {
+    let [exact, color, empty] = glyphs;
+    let pegs: Vec<String> = std::iter::repeat_n(exact, feedback.exact_matches)
+        .chain(std::iter::repeat_n(color, feedback.color_matches))
+        .chain(std::iter::repeat(empty))
+        .take(code_length)
+        .map(String::from)
+        .collect();
+    let (top, bottom) = pegs.split_at(code_length.div_ceil(2));
+    let width = top.len() * 2 - 1;
+    [top.join(" "), format!("{:<width$}", bottom.join(" "), width = width)]
+}
+
+/// Print a peg for the color character in the current [`RenderStyle`]
+pub fn print_colored_symbol(color_char: char) {
+    match style().peg(color_char) {
+        (Some(color), glyph) => execute!(
+            io::stdout(),
+            SetForegroundColor(color),
+            Print(glyph),
+            ResetColor
+        )
+        .unwrap(),
+        (None, glyph) => out!("{}", glyph),
+    }
+}
+
+/// Render a guess with its feedback as key pegs and/or text, depending on
+/// the [`FeedbackStyle`]
+pub fn show_guess_result(attempts: usize, guess: &Code, feedback: &Feedback) {
+    let label = format!("  Guess {}: ", attempts);
+    out!("{}", label);
+    for color in guess.symbols() {
+        print_colored_symbol(color);
+        out!(" ");
+    }
+
+    if feedback_style().shows_pegs() {
+        let [top, bottom] = key_peg_grid(feedback, guess.len(), style().key_glyphs());
+        let indent = label.chars().count() + 2 * guess.len() + 2;
+        outln!("  {}", top);
+        outln!("{:indent$}{}", "", bottom, indent = indent);
+    } else {
+        outln!();
+    }
+    if feedback_style().shows_text() {
+        outln!("  → {}", feedback_text(feedback));
+    }
+}
+
+/// An encouraging line for a guess's feedback
+pub fn hint_message(feedback: &Feedback) -> &'static str {
+    match (feedback.exact_matches, feedback.color_matches) {
+        (0, 0) => "💭 Hmm, try completely different colors!",
+        (0, _) => "💡 You have the right colors, just wrong positions!",
+        (1, _) => "🎯 Getting warmer! One's in the right spot!",
+        (2, _) => "🔥 Nice! Two are perfectly placed!",
+        (3, _) => "⚡ So close! Just one more to go!",
+        _ => "🎲 Keep analyzing the patterns...",
+    }
 }
 
 /// Print encouraging hints based on feedback
-pub fn print_hint(feedback: &Feedback, attempts: usize, max_attempts: usize) {
-    let Feedback {
-        exact_matches,
-        color_matches,
-    } = *feedback;
-
-    let message = match (exact_matches, color_matches) {
-        (0, 0) => "  💭 Hmm, try completely different colors!",
-        (0, _) => "  💡 You have the right colors, just wrong positions!",
-        (1, _) => "  🎯 Getting warmer! One's in the right spot!",
-        (2, _) => "  🔥 Nice! Two are perfectly placed!",
-        (3, _) => "  ⚡ So close! Just one more to go!",
-        _ => "  🎲 Keep analyzing the patterns...",
-    };
+pub fn print_hint(feedback: &Feedback, remaining_attempts: usize) {
+    outln!("  {}", hint_message(feedback));
+
+    if remaining_attempts <= 2 {
+        outln!("  ⏰ Running out of guesses!");
+    }
+}
 
-    println!("{}", message);
+/// Most remaining candidates worth listing individually.
+const MAX_LISTED_CANDIDATES: usize = 6;
 
-    if attempts >= max_attempts.saturating_sub(2) {
-        println!("  ⏰ Running out of guesses!");
+/// Show how many codes are still consistent with every clue so far
+pub fn print_remaining(candidates: &[Code]) {
+    match candidates.len() {
+        0 => outln!("  🔎 No code matches every clue — that shouldn't happen!"),
+        1 => outln!("  🔎 Only one code fits every clue!"),
+        n => outln!("  🔎 {} codes still fit every clue", n),
+    }
+
+    if candidates.len() <= MAX_LISTED_CANDIDATES {
+        for code in candidates {
+            out!("     ");
+            for color in code.symbols() {
+                print_colored_symbol(color);
+                out!(" ");
+            }
+            outln!(" {}", code);
+        }
     }
 }
 
+/// Show the solver's suggested guess and how much it is expected to reveal
+pub fn show_hint(guess: &Code, bits: f64) {
+    out!("  🧭 Try: ");
+    for color in guess.symbols() {
+        print_colored_symbol(color);
+        out!(" ");
+    }
+    outln!(
+        " {}  (expected {:.2} bits of information)",
+        guess,
+        bits
+    );
+}
+
 /// Reveal the secret code
-pub fn reveal_code(secret_code: &[char]) {
-    print!("  The code was: ");
-    for &color in secret_code {
+pub fn reveal_code(secret_code: &Code) {
+    out!("  The code was: ");
+    for color in secret_code.symbols() {
         print_colored_symbol(color);
-        print!(" ");
+        out!(" ");
+    }
+    outln!();
+}
+
+/// Show the seed that reproduces this game's secret
+pub fn show_seed(seed: Option<u64>) {
+    if let Some(seed) = seed {
+        outln!("🌱 Seed: {} (replay with --seed {})", seed, seed);
+    }
+}
+
+/// Show how the built-in solver would have played the same secret
+pub fn show_solver_comparison(solver_guesses: &[Code]) {
+    out!(
+        "\n🤖 The Knuth solver cracks it in {} {}: ",
+        solver_guesses.len(),
+        if solver_guesses.len() == 1 { "guess" } else { "guesses" }
+    );
+    for (i, guess) in solver_guesses.iter().enumerate() {
+        if i > 0 {
+            out!("→ ");
+        }
+        for color in guess.symbols() {
+            print_colored_symbol(color);
+        }
+        out!(" ");
     }
-    println!();
+    outln!();
 }
 
-/// Prompt the player to choose a difficulty at the start of a game.
-pub fn select_difficulty() -> Difficulty {
-    println!("\n🎚️  Choose your difficulty:");
+/// Print the post-game report grading each guess against the solver
+pub fn show_analysis(reviews: &[GuessReview]) {
+    if reviews.is_empty() {
+        return;
+    }
+
+    outln!("\n📊 Game analysis:");
+    for (i, review) in reviews.iter().enumerate() {
+        out!("  {}. ", i + 1);
+        for color in review.guess.symbols() {
+            print_colored_symbol(color);
+        }
+        outln!(
+            " {}  → {} exact, {} color{}",
+            review.guess,
+            review.feedback.exact_matches,
+            review.feedback.color_matches,
+            if review.feedback.color_matches != 1 { "s" } else { "" }
+        );
+        outln!(
+            "     {} → {} codes left (at most {}; Knuth's pick: at most {})",
+            review.candidates_before,
+            review.candidates_after,
+            review.worst_case,
+            review.solver_worst_case
+        );
+        outln!(
+            "     Knuth's solver needs {} more from here (an upper bound, not the optimum)",
+            review.knuth_upper_bound
+        );
+        if !review.consistent {
+            outln!("     ⚠️  Couldn't be the secret — it contradicts earlier feedback");
+        }
+    }
+}
+
+/// Prompt the player to choose who sets the code, or to see their stats or
+/// the leaderboard.
+pub fn select_mode() -> MenuChoice {
+    outln!("\n🎭 Choose your role:");
+    outln!("  1. Codebreaker — crack my secret code");
+    outln!("  2. Codemaker — think of a code and I'll crack it");
+    outln!("  3. Stats — see your record");
+    outln!("  4. Leaderboard — best wins on this machine");
+
+    loop {
+        out!("\n👉 Enter 1-4 (default 1): ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+
+        match input.trim() {
+            "" | "1" => return MenuChoice::Play(Mode::Codebreaker),
+            "2" => return MenuChoice::Play(Mode::Codemaker),
+            "3" => return MenuChoice::Stats,
+            "4" => return MenuChoice::Leaderboard,
+            _ => outln!("  ❌ Please enter a number between 1 and 4."),
+        }
+    }
+}
+
+/// What the player picked from the opening menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuChoice {
+    /// Start a game in this role.
+    Play(Mode),
+    /// Look at (or reset) the stats.
+    Stats,
+    /// Look at the high-score table.
+    Leaderboard,
+}
+
+/// The answer to the play-again prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayAgain {
+    Yes,
+    No,
+    /// Show the stats, then ask again.
+    Stats,
+}
+
+/// What the player picked from the difficulty menu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Selection {
+    /// A regular game at this difficulty.
+    Standard(Difficulty),
+    /// Today's shared daily challenge at this preset.
+    Daily(Difficulty),
+}
+
+/// Prompt the player to choose a difficulty at the start of a game, offering
+/// the daily challenge when `allow_daily` is set.
+pub fn select_difficulty(allow_daily: bool) -> Selection {
+    let custom_option = Difficulty::ALL.len() + 1;
+    let daily_option = custom_option + 1;
+    let last_option = if allow_daily { daily_option } else { custom_option };
+
+    outln!("\n🎚️  Choose your difficulty:");
     for (i, d) in Difficulty::ALL.iter().enumerate() {
-        println!(
+        outln!(
             "  {}. {} — {} slots · {} colors · {} guesses",
             i + 1,
             d.name,
@@ -91,9 +527,13 @@ pub fn select_difficulty() -> Difficulty {
             d.max_attempts
         );
     }
+    outln!("  {}. Custom — pick your own slots, colors and guesses", custom_option);
+    if allow_daily {
+        outln!("  {}. Daily — today's shared puzzle, one attempt per day", daily_option);
+    }
 
-    loop {
-        print!("\n👉 Enter 1-{} (default {}): ", Difficulty::ALL.len(), 2);
+    let difficulty = loop {
+        out!("\n👉 Enter 1-{} (default {}): ", last_option, 2);
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
@@ -102,60 +542,698 @@ pub fn select_difficulty() -> Difficulty {
 
         // Empty input picks the Classic default (option 2).
         if input.is_empty() {
-            return Difficulty::CLASSIC;
+            break Difficulty::CLASSIC;
+        }
+
+        match input.parse::<usize>() {
+            Ok(n) if (1..=Difficulty::ALL.len()).contains(&n) => break Difficulty::ALL[n - 1],
+            Ok(n) if n == custom_option => break build_custom_difficulty(),
+            Ok(n) if allow_daily && n == daily_option => {
+                return Selection::Daily(select_daily_preset())
+            }
+            _ => outln!("  ❌ Please enter a number between 1 and {}.", last_option),
         }
+    };
+
+    Selection::Standard(select_repeats(select_blanks(difficulty)))
+}
+
+/// Ask which preset to play today's challenge at. Rules stay at the preset's
+/// defaults so everyone plays the same puzzle.
+fn select_daily_preset() -> Difficulty {
+    loop {
+        let names: Vec<&str> = Difficulty::ALL.iter().map(|d| d.name).collect();
+        out!(
+            "\n📅 Daily difficulty — {} (1-{}, default 2): ",
+            names.join(" / "),
+            Difficulty::ALL.len()
+        );
+        io::stdout().flush().unwrap();
 
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim();
+
+        if input.is_empty() {
+            return Difficulty::CLASSIC;
+        }
         match input.parse::<usize>() {
             Ok(n) if (1..=Difficulty::ALL.len()).contains(&n) => return Difficulty::ALL[n - 1],
-            _ => println!("  ❌ Please enter a number between 1 and {}.", Difficulty::ALL.len()),
+            _ => outln!("  ❌ Please enter a number between 1 and {}.", Difficulty::ALL.len()),
+        }
+    }
+}
+
+/// Ask whether slots may be left blank (off by default).
+fn select_blanks(difficulty: Difficulty) -> Difficulty {
+    loop {
+        out!(
+            "\n○ Allow blank slots ({} as an extra peg)? (y/N): ",
+            game::BLANK
+        );
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+
+        let blanks = match input.trim().to_lowercase().as_str() {
+            "" | "n" | "no" => false,
+            "y" | "yes" => true,
+            _ => {
+                outln!("  ❌ Please answer y or n.");
+                continue;
+            }
+        };
+
+        match difficulty.with_blanks(blanks) {
+            Ok(difficulty) => return difficulty,
+            Err(error) => outln!("  ❌ Invalid rule: {}.", error),
+        }
+    }
+}
+
+/// Ask whether colors may repeat, re-prompting if the palette is too small
+/// for the chosen rule.
+fn select_repeats(difficulty: Difficulty) -> Difficulty {
+    outln!("\n🔁 Repeated colors:");
+    for (i, repeats) in Repeats::ALL.iter().enumerate() {
+        outln!("  {}. {}", i + 1, capitalize(repeats.describe()));
+    }
+
+    loop {
+        out!("\n👉 Enter 1-{} (default 1): ", Repeats::ALL.len());
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim();
+
+        let repeats = match input.parse::<usize>() {
+            _ if input.is_empty() => Repeats::Allowed,
+            Ok(n) if (1..=Repeats::ALL.len()).contains(&n) => Repeats::ALL[n - 1],
+            _ => {
+                outln!("  ❌ Please enter a number between 1 and {}.", Repeats::ALL.len());
+                continue;
+            }
+        };
+
+        match difficulty.with_repeats(repeats) {
+            Ok(difficulty) => return difficulty,
+            Err(error) => outln!("  ❌ Invalid rule: {}.", error),
+        }
+    }
+}
+
+/// Upper-case the first letter of a menu description.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Ask for each custom difficulty setting until they form a playable game.
+fn build_custom_difficulty() -> Difficulty {
+    loop {
+        let code_length = prompt_number(&format!(
+            "Slots ({}-{})",
+            game::MIN_CODE_LENGTH,
+            game::MAX_CODE_LENGTH
+        ));
+        let num_colors = prompt_number(&format!("Colors (2-{})", game::COLORS.len()));
+        let max_attempts = prompt_number(&format!("Guesses (1-{})", game::MAX_ATTEMPTS));
+
+        match Difficulty::custom(code_length, num_colors, max_attempts) {
+            Ok(difficulty) => return difficulty,
+            Err(error) => outln!("  ❌ Invalid difficulty: {}.", error),
+        }
+    }
+}
+
+/// Prompt until the player enters a whole number.
+fn prompt_number(label: &str) -> usize {
+    loop {
+        out!("  👉 {}: ", label);
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+
+        match input.trim().parse() {
+            Ok(n) => return n,
+            Err(_) => outln!("  ❌ Please enter a whole number."),
         }
     }
 }
 
 /// Display the welcome banner for the chosen difficulty
 pub fn print_welcome(difficulty: &Difficulty) {
-    println!("\n╔════════════════════════════════════════════╗");
-    println!("║          🧩 CIPHERMIND 🧩                 ║");
-    println!("║   The Ultimate Code-Breaking Challenge    ║");
-    println!("╚════════════════════════════════════════════╝\n");
+    // The badge is as wide as the emoji so the frame lines up either way.
+    let badge = if style() == RenderStyle::Ascii { "**" } else { "🧩" };
+    outln!("\n╔════════════════════════════════════════════╗");
+    outln!("║          {} CIPHERMIND {}                  ║", badge, badge);
+    outln!("║   The Ultimate Code-Breaking Challenge     ║");
+    outln!("╚════════════════════════════════════════════╝\n");
 
-    println!("🎮 How to Play  [{}]:", difficulty.name);
-    println!(
+    outln!("🎮 How to Play  [{}]:", difficulty.name);
+    outln!(
         "  • I've created a secret {}-color code",
         difficulty.code_length
     );
-    println!("  • Available colors: ",);
-    print!("    ");
-    for &color in difficulty.colors() {
+    outln!("  • Rule: {}", difficulty.repeats.describe());
+    if difficulty.blanks {
+        outln!("  • Slots may be left blank — type {} for an empty slot", game::BLANK);
+    }
+    outln!("  • Available colors: ",);
+    out!("    ");
+    for color in difficulty.symbols() {
         print_colored_symbol(color);
-        print!(" = {} ", color);
+        out!(" = {} ", color);
     }
-    println!(
+    outln!(
         "\n  • You have {} guesses to crack it!",
         difficulty.max_attempts
     );
-    println!("  • After each guess, I'll tell you:");
-    println!("    - How many are EXACT (right color, right position)");
-    println!("    - How many are COLOR matches (right color, wrong position)");
+    outln!("  • After each guess, I'll tell you:");
+    outln!("    - How many are EXACT (right color, right position)");
+    outln!("    - How many are COLOR matches (right color, wrong position)");
     let example: String = difficulty
         .colors()
         .iter()
         .cycle()
         .take(difficulty.code_length)
         .collect();
-    println!(
+    outln!(
         "\n💡 Example: Enter your guess as {} letters, like: {}\n",
         difficulty.code_length, example
     );
 }
 
-/// Ask if the player wants to play again
-pub fn play_again() -> bool {
-    print!("\n🔄 Play again? (y/n): ");
+/// Where a codebreaker game is played: the full-screen board when stdin and
+/// stdout are terminals, otherwise the scrolling transcript.
+pub enum Screen {
+    Transcript,
+    Board(Board),
+}
+
+impl Screen {
+    /// Show the start of `game` (including the turns of a resumed game).
+    pub fn open(game: &Game) -> Screen {
+        if board::is_supported() {
+            match Board::open(game.difficulty().code_length) {
+                Ok(mut board) => {
+                    board.set_status("Pick a color for each slot, then press Enter.");
+                    return Screen::Board(board);
+                }
+                Err(error) => outln!("  ⚠️  Couldn't open the game board: {}", error),
+            }
+        }
+
+        print_welcome(game.difficulty());
+        for (i, turn) in game.history().iter().enumerate() {
+            show_guess_result(i + 1, &turn.guess, &turn.feedback);
+        }
+        Screen::Transcript
+    }
+
+    /// Read the next guess or command.
+    pub fn read_command(&mut self, game: &Game) -> String {
+        match self {
+            Screen::Board(board) => board.read_command(game).unwrap(),
+            Screen::Transcript => {
+                out!("\n🎯 Enter your guess ('hint' for help, 'save' to pause, 'quit' to exit): ");
+                io::stdout().flush().unwrap();
+
+                let mut input = String::new();
+                io::stdin().read_line(&mut input).unwrap();
+                input.trim().to_string()
+            }
+        }
+    }
+
+    /// Report a rejected guess or command.
+    pub fn show_error(&mut self, message: &str) {
+        match self {
+            Screen::Board(board) => board.set_status(format!("❌ {}", message)),
+            Screen::Transcript => outln!("  ❌ {}", message),
+        }
+    }
+
+    /// Explain why a guess was turned down, pointing the board's cursor at
+    /// the offending slot.
+    pub fn show_guess_error(&mut self, error: &GuessError) {
+        if let (
+            Screen::Board(board),
+            GuessError::IllegalSymbol { position, .. } | GuessError::Duplicate { position, .. },
+        ) = (&mut *self, error)
+        {
+            board.focus_slot(*position);
+        }
+        self.show_error(&error.to_string());
+    }
+
+    /// Show the solver's suggested guess.
+    pub fn show_hint(&mut self, guess: &Code, bits: f64) {
+        match self {
+            Screen::Board(board) => board.set_status(format!(
+                "🧭 Try {} (expected {:.2} bits of information)",
+                guess,
+                bits
+            )),
+            Screen::Transcript => show_hint(guess, bits),
+        }
+    }
+
+    /// Leave the screen once `game` is over. The board stays up, showing the
+    /// final row and its feedback, until a key is pressed.
+    pub fn close(self, game: &Game) {
+        if let Screen::Board(mut board) = self {
+            let status = if game.outcome() == Some(Outcome::Won) {
+                "🎉 Code cracked!"
+            } else {
+                "💥 Out of guesses."
+            };
+            board.finish(game, status).unwrap();
+        }
+    }
+
+    /// Show the turn just played, with encouragement while guesses remain.
+    pub fn show_turn(
+        &mut self,
+        game: &Game,
+        feedback: &Feedback,
+        candidates: Option<&[Code]>,
+    ) {
+        let Some(turn) = game.history().last() else {
+            return;
+        };
+        let playing = !game.is_over();
+        match self {
+            Screen::Board(board) => {
+                board.next_row();
+                let mut status = hint_message(feedback).to_string();
+                if let Some(candidates) = candidates.filter(|_| playing) {
+                    status.push_str(&format!(" · {} codes still fit", candidates.len()));
+                }
+                if playing && game.remaining_attempts() <= 2 {
+                    status.push_str(" · ⏰ Running out of guesses!");
+                }
+                board.set_status(status);
+            }
+            Screen::Transcript => {
+                show_guess_result(game.attempts(), &turn.guess, feedback);
+                if playing {
+                    print_hint(feedback, game.remaining_attempts());
+                    if let Some(candidates) = candidates {
+                        print_remaining(candidates);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Explain the codemaker rules for the chosen difficulty
+pub fn print_codemaker_welcome(difficulty: &Difficulty) {
+    outln!("\n🎮 Codemaker  [{}]:", difficulty.name);
+    outln!(
+        "  • Think of a secret {}-color code — {}",
+        difficulty.code_length,
+        difficulty.repeats.describe()
+    );
+    if difficulty.blanks {
+        outln!("  • Slots may be left blank ({})", game::BLANK);
+    }
+    out!("  • Available colors: ");
+    for color in difficulty.symbols() {
+        print_colored_symbol(color);
+        out!(" = {} ", color);
+    }
+    outln!(
+        "\n  • I get {} guesses to crack it",
+        difficulty.max_attempts
+    );
+    outln!("  • Score each guess as two numbers: EXACT then COLOR matches, like: 1 2\n");
+}
+
+/// Show the computer's guess and ask the player to score it. Returns `None`
+/// if the player types 'quit'.
+pub fn ask_feedback(turn: usize, guess: &Code, code_length: usize) -> Option<Feedback> {
+    out!("  Guess {}: ", turn);
+    for color in guess.symbols() {
+        print_colored_symbol(color);
+        out!(" ");
+    }
+    outln!(" {}", guess);
+
+    loop {
+        out!("  📝 Exact and color matches (or 'quit'): ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim();
+
+        if input.eq_ignore_ascii_case("quit") {
+            return None;
+        }
+
+        let numbers: Vec<usize> = input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .map_while(|part| part.parse().ok())
+            .collect();
+
+        match numbers[..] {
+            [exact, color] if exact + color <= code_length => {
+                return Some(Feedback {
+                    exact_matches: exact,
+                    color_matches: color,
+                })
+            }
+            [_, _] => outln!("  ❌ Exact plus color can't exceed {}.", code_length),
+            _ => outln!("  ❌ Please enter two numbers, like: 1 2"),
+        }
+    }
+}
+
+/// Explain which of the player's answers contradict each other
+pub fn show_conflict(turns: &[usize]) {
+    let numbers: Vec<String> = turns.iter().map(|turn| (turn + 1).to_string()).collect();
+    outln!("\n🤔 No code matches all your answers!");
+    match numbers.as_slice() {
+        [] => {}
+        [only] => outln!("  Your answer on turn {} can't happen for any code.", only),
+        [earlier @ .., last] => outln!(
+            "  Your answers on turns {} and {} can't all be right — one of them is a mistake.",
+            earlier.join(", "),
+            last
+        ),
+    }
+}
+
+/// Print the spoiler-free daily summary for sharing
+pub fn show_daily_share(text: &str) {
+    outln!("\n📋 Share your result:\n");
+    outln!("{}", text);
+}
+
+/// Ask whether to pick up the saved game (yes by default)
+pub fn confirm_resume() -> bool {
+    out!("\n📂 You have a saved game. Resume it? (Y/n): ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+
+    !matches!(input.trim().to_lowercase().as_str(), "n" | "no")
+}
+
+/// Ask if the player wants to play again (or look at their stats first)
+pub fn play_again() -> PlayAgain {
+    out!("\n🔄 Play again? (y/n, s for stats): ");
     io::stdout().flush().unwrap();
 
     let mut input = String::new();
     io::stdin().read_line(&mut input).unwrap();
 
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => PlayAgain::Yes,
+        "s" | "stats" => PlayAgain::Stats,
+        _ => PlayAgain::No,
+    }
+}
+
+/// Ask who is playing: pick a known profile or create a new one.
+pub fn select_profile(players: &[String]) -> String {
+    if !players.is_empty() {
+        outln!("\n👤 Who's playing?");
+        for (i, player) in players.iter().enumerate() {
+            outln!("  {}. {}", i + 1, player);
+        }
+        outln!("  {}. New player", players.len() + 1);
+
+        loop {
+            out!("\n👉 Enter 1-{} (default 1): ", players.len() + 1);
+            io::stdout().flush().unwrap();
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).unwrap();
+            let input = input.trim();
+
+            if input.is_empty() {
+                return players[0].clone();
+            }
+            match input.parse::<usize>() {
+                Ok(n) if (1..=players.len()).contains(&n) => return players[n - 1].clone(),
+                Ok(n) if n == players.len() + 1 => break,
+                _ => outln!("  ❌ Please enter a number between 1 and {}.", players.len() + 1),
+            }
+        }
+    }
+
+    loop {
+        out!("\n👤 Enter your name: ");
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+
+        match leaderboard::validate_name(&input) {
+            Ok(name) => return name,
+            Err(error) => outln!("  ❌ {}", error),
+        }
+    }
+}
+
+/// Format a game time as minutes and seconds
+pub fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Print the top wins for each preset difficulty
+pub fn show_leaderboard(board: &Leaderboard) {
+    outln!("\n🏆 Leaderboard (fewest guesses, then fastest):");
+    for difficulty in Difficulty::ALL {
+        outln!("\n  {}", difficulty.name);
+        let top = board.top(difficulty.name);
+        if top.is_empty() {
+            outln!("    No wins yet.");
+        }
+        for (i, entry) in top.iter().enumerate() {
+            outln!(
+                "    {:>2}. {:<width$}  {:>2} {}  {:>6}",
+                i + 1,
+                entry.player,
+                entry.guesses,
+                if entry.guesses == 1 { "guess  " } else { "guesses" },
+                format_time(entry.time),
+                width = leaderboard::MAX_NAME_LENGTH
+            );
+        }
+    }
+}
+
+/// Width of the longest bar in the win distribution.
+const MAX_BAR_WIDTH: usize = 20;
+
+/// Print the record for every difficulty played so far
+pub fn show_stats(stats: &Stats) {
+    outln!("\n📈 Your stats:");
+    if stats.by_difficulty.is_empty() {
+        outln!("  No finished games yet — go crack some codes!");
+        return;
+    }
+
+    for (name, s) in &stats.by_difficulty {
+        outln!(
+            "\n  {} — {} played · {:.0}% won · streak {} (best {})",
+            name,
+            s.played,
+            s.win_rate() * 100.0,
+            s.current_streak,
+            s.best_streak
+        );
+
+        let most = s.distribution.iter().copied().max().unwrap_or(0).max(1);
+        for (label, &wins) in stats::BUCKET_LABELS.iter().zip(&s.distribution) {
+            let width = (wins * MAX_BAR_WIDTH).div_ceil(most);
+            outln!("    {:>3} │{} {}", label, "█".repeat(width), wins);
+        }
+    }
+}
+
+/// After the stats screen: `true` if the player asked to reset (and confirmed)
+pub fn confirm_stats_reset() -> bool {
+    out!("\n  Press Enter to go back, or type 'reset' to clear your stats: ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    if !input.trim().eq_ignore_ascii_case("reset") {
+        return false;
+    }
+
+    out!("  ⚠️  This erases every recorded game. Are you sure? (y/N): ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
     matches!(input.trim().to_lowercase().as_str(), "y" | "yes")
 }
+
+/// Introduce a recorded game before stepping through it
+pub fn show_replay_header(record: &Record) {
+    let game = &record.game;
+    let d = game.difficulty();
+    outln!("\n📼 Replaying {}'s {} game", record.player, d.name);
+    outln!(
+        "  {} slots · {} colors · {} guesses · {}{}",
+        d.code_length,
+        d.num_colors,
+        d.max_attempts,
+        d.repeats.describe(),
+        if d.blanks { " · blanks allowed" } else { "" }
+    );
+    if game.hints_used() > 0 {
+        outln!("  Hints used: {}", game.hints_used());
+    }
+}
+
+/// Wait before the next recorded turn: `false` if the viewer typed `q`
+pub fn next_turn() -> bool {
+    out!("\n⏭️  Press Enter for the next turn, or q to stop: ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    outln!();
+    !input.trim().eq_ignore_ascii_case("q")
+}
+
+/// Show when a recorded turn happened, relative to the start of the game
+pub fn show_turn_time(offset: Duration) {
+    outln!("  ⏱️  {}", format_time(offset));
+}
+
+/// Flag a recorded feedback that the secret doesn't actually produce
+pub fn show_feedback_mismatch(actual: &Feedback) {
+    outln!(
+        "  ⚠️  Recorded feedback is wrong — the secret gives {}",
+        feedback_text(actual)
+    );
+}
+
+/// Close a replay with the outcome and any feedback problems found
+pub fn show_replay_summary(record: &Record, mismatches: usize) {
+    let game = &record.game;
+    outln!("\n═══════════════════════════════════════════");
+    match record.outcome {
+        Outcome::Won => outln!(
+            "🎉 Won in {} {} ({}).",
+            game.attempts(),
+            if game.attempts() == 1 { "guess" } else { "guesses" },
+            format_time(game.elapsed())
+        ),
+        Outcome::Lost => outln!("💥 Lost after {} guesses.", game.attempts()),
+        Outcome::Abandoned => outln!("🚪 Abandoned after {} guesses.", game.attempts()),
+    }
+    if let Some(secret) = game.secret() {
+        reveal_code(secret);
+    }
+    show_seed(game.seed());
+    if mismatches == 0 {
+        outln!("✅ Every recorded feedback matches the secret.");
+    } else {
+        outln!(
+            "❌ {} recorded {} the secret.",
+            mismatches,
+            if mismatches == 1 { "feedback doesn't match" } else { "feedbacks don't match" }
+        );
+    }
+    outln!("═══════════════════════════════════════════");
+}
+
+/// Say goodbye when the player leaves
+pub fn print_goodbye() {
+    outln!("\n👋 Thanks for playing CipherMind!");
+    outln!("Remember: Logic conquers all codes! 🧩\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_names_round_trip() {
+        for style in RenderStyle::ALL {
+            assert_eq!(RenderStyle::from_name(style.name()), Some(style));
+        }
+        assert_eq!(RenderStyle::from_name("ASCII"), Some(RenderStyle::Ascii));
+        assert_eq!(RenderStyle::from_name("rainbow"), None);
+    }
+
+    #[test]
+    fn test_every_color_has_its_own_shape() {
+        let mut symbols = game::COLORS.to_vec();
+        symbols.push(game::BLANK);
+        let shapes: Vec<char> = symbols.iter().map(|&c| RenderStyle::Shapes.peg(c).1).collect();
+        for (i, shape) in shapes.iter().enumerate() {
+            assert!(!shapes[..i].contains(shape), "{} is used twice", shape);
+        }
+    }
+
+    #[test]
+    fn test_key_peg_grid() {
+        let glyphs = RenderStyle::Color.key_glyphs();
+        let feedback = Feedback {
+            exact_matches: 1,
+            color_matches: 2,
+        };
+        assert_eq!(key_peg_grid(&feedback, 4, glyphs), ["● ○", "○ ·"]);
+        assert_eq!(key_peg_grid(&feedback, 5, glyphs), ["● ○ ○", "· ·  "]);
+        assert_eq!(
+            key_peg_grid(&feedback, 5, RenderStyle::Ascii.key_glyphs()),
+            ["X o o", ". .  "]
+        );
+    }
+
+    #[test]
+    fn test_feedback_style_names_round_trip() {
+        for style in FeedbackStyle::ALL {
+            assert_eq!(FeedbackStyle::from_name(style.name()), Some(style));
+        }
+        assert!(FeedbackStyle::Both.shows_text() && FeedbackStyle::Both.shows_pegs());
+        assert!(!FeedbackStyle::Text.shows_pegs());
+        assert!(!FeedbackStyle::Pegs.shows_text());
+    }
+
+    #[test]
+    fn test_ascii_style_has_no_color() {
+        for &c in game::COLORS.iter().chain([&game::BLANK]) {
+            let (color, glyph) = RenderStyle::Ascii.peg(c);
+            assert_eq!(color, None);
+            assert_eq!(glyph, c);
+            assert!(glyph.is_ascii());
+        }
+        assert!(RenderStyle::Ascii.key_glyphs().iter().all(char::is_ascii));
+        assert!(RenderStyle::Ascii.cursor().is_ascii());
+    }
+
+    #[test]
+    fn test_ascii_style_prints_only_ascii() {
+        let ascii = RenderStyle::Ascii;
+        assert_eq!(ascii.text("  ❌ Please answer y or n."), "  Please answer y or n.");
+        assert_eq!(ascii.text("  ⚠️  Couldn't save"), "  Couldn't save");
+        assert_eq!(ascii.text("Saved 💾  to disk"), "Saved to disk");
+        assert_eq!(ascii.text("🎉 CONGRATULATIONS! 🎉"), "CONGRATULATIONS!");
+        assert_eq!(ascii.text("all codes! 🧩\n"), "all codes!\n");
+        assert_eq!(ascii.text("═══"), "===");
+        assert_eq!(ascii.text("CIPHERMIND · Easy"), "CIPHERMIND - Easy");
+        assert_eq!(ascii.text("←/→ slot · ↑/↓ color"), "<-/-> slot - ^/v color");
+        assert_eq!(ascii.text("🟩🟨⬛⬛"), "Xo..");
+        assert_eq!(RenderStyle::Color.text("🎉 ●"), "🎉 ●");
+    }
+}